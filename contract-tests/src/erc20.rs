@@ -5,18 +5,43 @@ use blake2::{
     VarBlake2b,
 };
 use casper_engine_test_support::{Code, SessionBuilder, TestContext, TestContextBuilder};
+#[cfg(feature = "u256")]
+use casper_types::U256;
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, runtime_args, AsymmetricType, CLTyped,
-    ContractHash, Key, PublicKey, RuntimeArgs, U512,
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    crypto, runtime_args, AsymmetricType, CLType, CLTyped, ContractHash, ContractPackageHash, Key,
+    PublicKey, RuntimeArgs, SecretKey, U512,
 };
 
+/// Type of a token amount, which has to match the `u256` feature the token was built with.
+#[cfg(feature = "u256")]
+pub type TokenAmount = U256;
+
+/// Type of a token amount, which has to match the `u256` feature the token was built with.
+#[cfg(not(feature = "u256"))]
+pub type TokenAmount = U512;
+
 const CONTRACT_KEY: &str = "contract";
+const CONTRACT_PACKAGE_KEY: &str = "contract_package";
+const TEST_CONTRACT_KEY: &str = "erc20_test_contract";
+const TEST_CONTRACT_PACKAGE_KEY: &str = "erc20_test_contract_package";
 
 pub mod erc20_args {
     pub const ARG_NAME: &str = "name";
     pub const ARG_SYMBOL: &str = "symbol";
     pub const ARG_DECIMALS: &str = "decimals";
     pub const ARG_TOTAL_SUPPLY: &str = "total_supply";
+    pub const ARG_MINTABLE: &str = "mintable";
+    pub const ARG_WRAPPED: &str = "wrapped";
+    pub const ARG_UPGRADE: &str = "upgrade";
+    pub const ARG_CAP: &str = "cap";
+}
+
+pub mod roles {
+    pub const ADMIN: &str = "admin";
+    pub const MINTER: &str = "minter";
+    pub const PAUSER: &str = "pauser";
 }
 
 pub mod token_cfg {
@@ -24,9 +49,15 @@ pub mod token_cfg {
     pub const NAME: &str = "ERC20";
     pub const SYMBOL: &str = "ERC";
     pub const DECIMALS: u8 = 8;
-    pub fn total_supply() -> U512 {
+    pub const MINTABLE: bool = true;
+    pub fn total_supply() -> TokenAmount {
         1_000.into()
     }
+    pub fn cap() -> Option<TokenAmount> {
+        None
+    }
+    /// Decimals of a wrapped token, which match the decimals of CSPR.
+    pub const WRAPPED_DECIMALS: u8 = 9;
 }
 
 fn blake2b256(item_key_string: &[u8]) -> Box<[u8]> {
@@ -35,11 +66,148 @@ fn blake2b256(item_key_string: &[u8]) -> Box<[u8]> {
     hasher.finalize_boxed()
 }
 
+/// Computes a name of a named key the installer keeps for a token named `token_name`.
+fn installer_key(token_name: &str, key: &str) -> String {
+    format!("{}_{}", token_name, key)
+}
+
+/// Computes a dictionary item key of a token holder which is either a [`Key::Account`] or a [`Key::Hash`].
+fn address_item_key(address: Key) -> String {
+    let preimage = address.to_bytes().unwrap();
+    hex::encode(blake2b256(&preimage))
+}
+
+/// Event emitted by the ERC20 contract.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Event {
+    Transfer {
+        from: Option<Key>,
+        to: Option<Key>,
+        amount: TokenAmount,
+    },
+    Approval {
+        owner: Key,
+        spender: Key,
+        amount: TokenAmount,
+    },
+    Snapshot {
+        id: u64,
+    },
+    DelegateChanged {
+        delegator: Key,
+        from_delegate: Option<Key>,
+        to_delegate: Key,
+    },
+}
+
+impl CLTyped for Event {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl FromBytes for Event {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            0 => {
+                let (from, remainder) = Option::<Key>::from_bytes(remainder)?;
+                let (to, remainder) = Option::<Key>::from_bytes(remainder)?;
+                let (amount, remainder) = TokenAmount::from_bytes(remainder)?;
+                Ok((Event::Transfer { from, to, amount }, remainder))
+            }
+            1 => {
+                let (owner, remainder) = Key::from_bytes(remainder)?;
+                let (spender, remainder) = Key::from_bytes(remainder)?;
+                let (amount, remainder) = TokenAmount::from_bytes(remainder)?;
+                let event = Event::Approval {
+                    owner,
+                    spender,
+                    amount,
+                };
+                Ok((event, remainder))
+            }
+            2 => {
+                let (id, remainder) = u64::from_bytes(remainder)?;
+                Ok((Event::Snapshot { id }, remainder))
+            }
+            3 => {
+                let (delegator, remainder) = Key::from_bytes(remainder)?;
+                let (from_delegate, remainder) = Option::<Key>::from_bytes(remainder)?;
+                let (to_delegate, remainder) = Key::from_bytes(remainder)?;
+                let event = Event::DelegateChanged {
+                    delegator,
+                    from_delegate,
+                    to_delegate,
+                };
+                Ok((event, remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// Allowance of a spender over the tokens of an owner.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Allowance {
+    pub amount: TokenAmount,
+    pub expires_at: Option<u64>,
+}
+
+impl CLTyped for Allowance {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl FromBytes for Allowance {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (amount, remainder) = TokenAmount::from_bytes(bytes)?;
+        let (expires_at, remainder) = Option::<u64>::from_bytes(remainder)?;
+        Ok((Allowance { amount, expires_at }, remainder))
+    }
+}
+
+/// Vesting schedule of a beneficiary.
+#[derive(Debug, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub total: TokenAmount,
+    pub released: TokenAmount,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+impl CLTyped for VestingSchedule {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl FromBytes for VestingSchedule {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (total, remainder) = TokenAmount::from_bytes(bytes)?;
+        let (released, remainder) = TokenAmount::from_bytes(remainder)?;
+        let (start, remainder) = u64::from_bytes(remainder)?;
+        let (cliff, remainder) = u64::from_bytes(remainder)?;
+        let (duration, remainder) = u64::from_bytes(remainder)?;
+        let vesting_schedule = VestingSchedule {
+            total,
+            released,
+            start,
+            cliff,
+            duration,
+        };
+        Ok((vesting_schedule, remainder))
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct Sender(pub AccountHash);
 
 pub struct Token {
     context: TestContext,
+    block_time: u64,
     pub ali: AccountHash,
     pub bob: AccountHash,
     pub joe: AccountHash,
@@ -47,6 +215,41 @@ pub struct Token {
 
 impl Token {
     pub fn deploy() -> Token {
+        Token::deploy_with(token_cfg::MINTABLE, token_cfg::cap())
+    }
+
+    pub fn deploy_with(mintable: bool, cap: Option<TokenAmount>) -> Token {
+        Token::install(runtime_args! {
+            erc20_args::ARG_NAME => token_cfg::NAME,
+            erc20_args::ARG_SYMBOL => token_cfg::SYMBOL,
+            erc20_args::ARG_DECIMALS => token_cfg::DECIMALS,
+            erc20_args::ARG_TOTAL_SUPPLY => token_cfg::total_supply(),
+            erc20_args::ARG_MINTABLE => mintable,
+            erc20_args::ARG_WRAPPED => false,
+            erc20_args::ARG_CAP => cap,
+            erc20_args::ARG_UPGRADE => false
+        })
+    }
+
+    /// Deploys a token wrapping CSPR, which has no initial supply and can't be minted.
+    pub fn deploy_wrapped() -> Token {
+        Token::deploy_wrapped_with(TokenAmount::zero(), false)
+    }
+
+    pub fn deploy_wrapped_with(total_supply: TokenAmount, mintable: bool) -> Token {
+        Token::install(runtime_args! {
+            erc20_args::ARG_NAME => token_cfg::NAME,
+            erc20_args::ARG_SYMBOL => token_cfg::SYMBOL,
+            erc20_args::ARG_DECIMALS => token_cfg::WRAPPED_DECIMALS,
+            erc20_args::ARG_TOTAL_SUPPLY => total_supply,
+            erc20_args::ARG_MINTABLE => mintable,
+            erc20_args::ARG_WRAPPED => true,
+            erc20_args::ARG_CAP => Option::<TokenAmount>::None,
+            erc20_args::ARG_UPGRADE => false
+        })
+    }
+
+    fn install(session_args: RuntimeArgs) -> Token {
         let ali = PublicKey::ed25519_from_bytes([3u8; 32]).unwrap();
         let bob = PublicKey::ed25519_from_bytes([6u8; 32]).unwrap();
         let joe = PublicKey::ed25519_from_bytes([9u8; 32]).unwrap();
@@ -57,45 +260,130 @@ impl Token {
             .build();
 
         let session_code = Code::from("erc20.wasm");
-        let session_args = runtime_args! {
-            erc20_args::ARG_NAME => token_cfg::NAME,
-            erc20_args::ARG_SYMBOL => token_cfg::SYMBOL,
-            erc20_args::ARG_DECIMALS => token_cfg::DECIMALS,
-            erc20_args::ARG_TOTAL_SUPPLY => token_cfg::total_supply()
-        };
-
         let session = SessionBuilder::new(session_code, session_args)
             .with_address(ali.to_account_hash())
             .with_authorization_keys(&[ali.to_account_hash()])
             .build();
 
         context.run(session);
+
+        let test_contract_code = Code::from("erc20_test_contract.wasm");
+        let session = SessionBuilder::new(test_contract_code, RuntimeArgs::new())
+            .with_address(ali.to_account_hash())
+            .with_authorization_keys(&[ali.to_account_hash()])
+            .build();
+
+        context.run(session);
+
         Token {
             context,
+            block_time: 0,
             ali: ali.to_account_hash(),
             bob: bob.to_account_hash(),
             joe: joe.to_account_hash(),
         }
     }
 
-    fn contract_hash(&self) -> ContractHash {
+    fn named_key_hash(&self, name: &str) -> [u8; 32] {
         self.context
             .get_account(self.ali)
             .unwrap()
             .named_keys()
-            .get(CONTRACT_KEY)
+            .get(name)
             .unwrap()
             .normalize()
             .into_hash()
             .unwrap()
+    }
+
+    pub fn contract_hash(&self) -> ContractHash {
+        self.named_key_hash(&installer_key(token_cfg::NAME, CONTRACT_KEY))
             .into()
     }
 
+    /// Returns an address of the token contract package.
+    pub fn contract_package(&self) -> Key {
+        Key::Hash(self.named_key_hash(&installer_key(token_cfg::NAME, CONTRACT_PACKAGE_KEY)))
+    }
+
+    /// Adds a new version of the token contract as the installer.
+    pub fn upgrade(&mut self, mintable: bool) {
+        let session_code = Code::from("erc20.wasm");
+        let session_args = runtime_args! {
+            erc20_args::ARG_NAME => token_cfg::NAME,
+            erc20_args::ARG_MINTABLE => mintable,
+            erc20_args::ARG_UPGRADE => true
+        };
+
+        let session = SessionBuilder::new(session_code, session_args)
+            .with_address(self.ali)
+            .with_authorization_keys(&[self.ali])
+            .with_block_time(self.block_time)
+            .build();
+
+        self.context.run(session);
+    }
+
+    /// Installs another token named `name` as the installer of this token.
+    pub fn install_another(&mut self, name: &str, symbol: &str) {
+        let session_code = Code::from("erc20.wasm");
+        let session_args = runtime_args! {
+            erc20_args::ARG_NAME => name,
+            erc20_args::ARG_SYMBOL => symbol,
+            erc20_args::ARG_DECIMALS => token_cfg::DECIMALS,
+            erc20_args::ARG_TOTAL_SUPPLY => token_cfg::total_supply(),
+            erc20_args::ARG_MINTABLE => token_cfg::MINTABLE,
+            erc20_args::ARG_WRAPPED => false,
+            erc20_args::ARG_CAP => token_cfg::cap(),
+            erc20_args::ARG_UPGRADE => false
+        };
+
+        let session = SessionBuilder::new(session_code, session_args)
+            .with_address(self.ali)
+            .with_authorization_keys(&[self.ali])
+            .with_block_time(self.block_time)
+            .build();
+
+        self.context.run(session);
+    }
+
+    /// Reads the symbol of another token installed by the installer of this token.
+    pub fn symbol_of(&self, token_name: &str) -> String {
+        self.context
+            .query(
+                self.ali,
+                &[
+                    installer_key(token_name, CONTRACT_KEY),
+                    "symbol".to_string(),
+                ],
+            )
+            .unwrap()
+            .into_t()
+            .unwrap()
+    }
+
+    /// Sets the block time of every following deploy.
+    pub fn set_block_time(&mut self, block_time: u64) {
+        self.block_time = block_time;
+    }
+
+    fn test_contract_hash(&self) -> ContractHash {
+        self.named_key_hash(TEST_CONTRACT_KEY).into()
+    }
+
+    /// Returns an address of the test contract which is able to hold tokens.
+    pub fn test_contract(&self) -> Key {
+        Key::Hash(self.named_key_hash(TEST_CONTRACT_PACKAGE_KEY))
+    }
+
     fn query_contract<T: CLTyped + FromBytes>(&self, name: &str) -> Option<T> {
-        match self
-            .context
-            .query(self.ali, &[CONTRACT_KEY.to_string(), name.to_string()])
-        {
+        match self.context.query(
+            self.ali,
+            &[
+                installer_key(token_cfg::NAME, CONTRACT_KEY),
+                name.to_string(),
+            ],
+        ) {
             Err(_) => None,
             Ok(maybe_value) => {
                 let value = maybe_value
@@ -107,11 +395,28 @@ impl Token {
     }
 
     fn call(&mut self, sender: Sender, method: &str, args: RuntimeArgs) {
+        let contract_hash = self.contract_hash();
+        self.call_contract(contract_hash, sender, method, args);
+    }
+
+    fn call_test_contract(&mut self, sender: Sender, method: &str, args: RuntimeArgs) {
+        let test_contract_hash = self.test_contract_hash();
+        self.call_contract(test_contract_hash, sender, method, args);
+    }
+
+    fn call_contract(
+        &mut self,
+        contract_hash: ContractHash,
+        sender: Sender,
+        method: &str,
+        args: RuntimeArgs,
+    ) {
         let Sender(address) = sender;
-        let code = Code::Hash(self.contract_hash().value(), method.to_string());
+        let code = Code::Hash(contract_hash.value(), method.to_string());
         let session = SessionBuilder::new(code, args)
             .with_address(address)
             .with_authorization_keys(&[address])
+            .with_block_time(self.block_time)
             .build();
         self.context.run(session);
     }
@@ -128,24 +433,134 @@ impl Token {
         self.query_contract("decimals").unwrap()
     }
 
-    pub fn balance_of(&self, account: AccountHash) -> Option<U512> {
+    pub fn total_supply(&self) -> TokenAmount {
+        self.query_contract("total_supply").unwrap()
+    }
+
+    pub fn snapshot_id(&self) -> u64 {
+        self.query_contract("snapshot_id").unwrap()
+    }
+
+    /// Reads the total supply at a snapshot through the `total_supply_at` entry point.
+    pub fn total_supply_at(&mut self, snapshot_id: u64) -> TokenAmount {
+        let token_contract = self.contract_hash();
+        self.call_test_contract(
+            Sender(self.ali),
+            "store_total_supply_at",
+            runtime_args! {
+                "token_contract" => token_contract,
+                "snapshot_id" => snapshot_id
+            },
+        );
+
+        self.context
+            .query(
+                self.ali,
+                &[TEST_CONTRACT_KEY.to_string(), "total_supply_at".to_string()],
+            )
+            .unwrap()
+            .into_t()
+            .unwrap()
+    }
+
+    pub fn cap(&self) -> Option<TokenAmount> {
+        self.query_contract("cap").unwrap()
+    }
+
+    pub fn balance_of<A: Into<Key>>(&self, address: A) -> Option<TokenAmount> {
+        let balance_item_key = address_item_key(address.into());
+
         let key = Key::Hash(self.contract_hash().value());
         let value = self
             .context
-            .query_dictionary_item(key, Some("balances".to_string()), account.to_string())
+            .query_dictionary_item(key, Some("balances".to_string()), balance_item_key)
             .ok()?;
 
-        Some(value.into_t::<U512>().unwrap())
+        Some(value.into_t::<TokenAmount>().unwrap())
     }
 
-    pub fn allowance(&self, owner: AccountHash, spender: AccountHash) -> Option<U512> {
-        let mut preimage = [0; 64];
-        preimage[..32].copy_from_slice(owner.as_bytes());
-        preimage[32..].copy_from_slice(spender.as_bytes());
-        let key_bytes = blake2b256(&preimage);
-        hex::encode_to_slice(&key_bytes, &mut preimage).unwrap();
+    /// Reads the balance of an address at a snapshot through the `balance_of_at` entry point.
+    pub fn balance_of_at<A: Into<Key>>(&mut self, address: A, snapshot_id: u64) -> TokenAmount {
+        let token_contract = self.contract_hash();
+        self.call_test_contract(
+            Sender(self.ali),
+            "store_balance_of_at",
+            runtime_args! {
+                "token_contract" => token_contract,
+                "address" => address.into(),
+                "snapshot_id" => snapshot_id
+            },
+        );
+
+        self.context
+            .query(
+                self.ali,
+                &[TEST_CONTRACT_KEY.to_string(), "balance_at".to_string()],
+            )
+            .unwrap()
+            .into_t()
+            .unwrap()
+    }
+
+    pub fn delegates<A: Into<Key>>(&self, address: A) -> Option<Key> {
+        let delegate_item_key = address_item_key(address.into());
+
+        let key = Key::Hash(self.contract_hash().value());
+        let value = self
+            .context
+            .query_dictionary_item(key, Some("delegates".to_string()), delegate_item_key)
+            .ok()?;
+
+        Some(value.into_t::<Key>().unwrap())
+    }
+
+    /// Reads an item of the `vote_checkpoints` dictionary.
+    fn vote_checkpoints_item<T: CLTyped + FromBytes>(&self, item_key: String) -> Option<T> {
+        let key = Key::Hash(self.contract_hash().value());
+        self.context
+            .query_dictionary_item(key, Some("vote_checkpoints".to_string()), item_key)
+            .ok()
+            .map(|value| value.into_t::<T>().unwrap())
+    }
+
+    pub fn get_votes<A: Into<Key>>(&self, address: A) -> TokenAmount {
+        let address = address.into();
+        let count: u32 = self
+            .vote_checkpoints_item(address_item_key(address))
+            .unwrap_or_default();
+        if count == 0 {
+            return TokenAmount::zero();
+        }
 
-        let allowance_item_key = std::str::from_utf8(&preimage).unwrap().to_string();
+        let mut preimage = address.to_bytes().unwrap();
+        preimage.append(&mut (count - 1).to_bytes().unwrap());
+        let checkpoint_item_key = hex::encode(blake2b256(&preimage));
+
+        let (_, votes): (u64, TokenAmount) =
+            self.vote_checkpoints_item(checkpoint_item_key).unwrap();
+        votes
+    }
+
+    pub fn allowance<A: Into<Key>, B: Into<Key>>(
+        &self,
+        owner: A,
+        spender: B,
+    ) -> Option<TokenAmount> {
+        self.allowance_record(owner, spender)
+            .map(|allowance| allowance.amount)
+    }
+
+    /// Reads a stored allowance, regardless of whether it is expired.
+    pub fn allowance_record<A: Into<Key>, B: Into<Key>>(
+        &self,
+        owner: A,
+        spender: B,
+    ) -> Option<Allowance> {
+        let mut preimage = Vec::new();
+        preimage.append(&mut owner.into().to_bytes().unwrap());
+        preimage.append(&mut spender.into().to_bytes().unwrap());
+        let key_bytes = blake2b256(&preimage);
+        let allowance_item_key = hex::encode(&key_bytes);
 
         let key = Key::Hash(self.contract_hash().value());
 
@@ -154,44 +569,610 @@ impl Token {
             .query_dictionary_item(key, Some("allowances".to_string()), allowance_item_key)
             .ok()?;
 
-        Some(value.into_t::<U512>().unwrap())
+        Some(value.into_t::<Allowance>().unwrap())
     }
 
-    pub fn transfer(&mut self, recipient: AccountHash, amount: U512, sender: Sender) {
+    /// Reads all events emitted by the contract in order.
+    pub fn events(&self) -> Vec<Event> {
+        let event_count: u64 = self.query_contract("event_count").unwrap();
+        let key = Key::Hash(self.contract_hash().value());
+
+        (0..event_count)
+            .map(|index| {
+                self.context
+                    .query_dictionary_item(key, Some("events".to_string()), index.to_string())
+                    .unwrap()
+                    .into_t::<Event>()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    pub fn transfer<A: Into<Key>>(&mut self, recipient: A, amount: TokenAmount, sender: Sender) {
         self.call(
             sender,
             "transfer",
             runtime_args! {
-                "recipient" => recipient,
+                "recipient" => recipient.into(),
                 "amount" => amount
             },
         );
     }
 
-    pub fn approve(&mut self, spender: AccountHash, amount: U512, sender: Sender) {
+    pub fn batch_transfer(
+        &mut self,
+        recipients: Vec<Key>,
+        amounts: Vec<TokenAmount>,
+        sender: Sender,
+    ) {
+        self.call(
+            sender,
+            "batch_transfer",
+            runtime_args! {
+                "recipients" => recipients,
+                "amounts" => amounts
+            },
+        );
+    }
+
+    pub fn approve<A: Into<Key>>(&mut self, spender: A, amount: TokenAmount, sender: Sender) {
         self.call(
             sender,
             "approve",
             runtime_args! {
-                "spender" => spender,
+                "spender" => spender.into(),
                 "amount" => amount
             },
         );
     }
 
-    pub fn transfer_from(
+    pub fn approve_with_expiry<A: Into<Key>>(
         &mut self,
-        owner: AccountHash,
-        recipient: AccountHash,
-        amount: U512,
+        spender: A,
+        amount: TokenAmount,
+        expires_at: u64,
+        sender: Sender,
+    ) {
+        self.call(
+            sender,
+            "approve_with_expiry",
+            runtime_args! {
+                "spender" => spender.into(),
+                "amount" => amount,
+                "expires_at" => expires_at
+            },
+        );
+    }
+
+    pub fn increase_allowance<A: Into<Key>>(
+        &mut self,
+        spender: A,
+        amount: TokenAmount,
+        sender: Sender,
+    ) {
+        self.call(
+            sender,
+            "increase_allowance",
+            runtime_args! {
+                "spender" => spender.into(),
+                "amount" => amount
+            },
+        );
+    }
+
+    pub fn decrease_allowance<A: Into<Key>>(
+        &mut self,
+        spender: A,
+        amount: TokenAmount,
+        sender: Sender,
+    ) {
+        self.call(
+            sender,
+            "decrease_allowance",
+            runtime_args! {
+                "spender" => spender.into(),
+                "amount" => amount
+            },
+        );
+    }
+
+    pub fn transfer_from<A: Into<Key>, B: Into<Key>>(
+        &mut self,
+        owner: A,
+        recipient: B,
+        amount: TokenAmount,
         sender: Sender,
     ) {
         self.call(
             sender,
             "transfer_from",
             runtime_args! {
-                "owner" => owner,
+                "owner" => owner.into(),
+                "recipient" => recipient.into(),
+                "amount" => amount
+            },
+        );
+    }
+
+    pub fn has_role<A: Into<Key>>(&self, role: &str, address: A) -> bool {
+        let mut preimage = Vec::new();
+        preimage.append(&mut role.to_string().to_bytes().unwrap());
+        preimage.append(&mut address.into().to_bytes().unwrap());
+        let role_item_key = hex::encode(blake2b256(&preimage));
+
+        let key = Key::Hash(self.contract_hash().value());
+        self.context
+            .query_dictionary_item(key, Some("roles".to_string()), role_item_key)
+            .ok()
+            .map(|value| value.into_t::<bool>().unwrap())
+            .unwrap_or_default()
+    }
+
+    pub fn grant_role<A: Into<Key>>(&mut self, role: &str, address: A, sender: Sender) {
+        self.call(
+            sender,
+            "grant_role",
+            runtime_args! {
+                "role" => role.to_string(),
+                "address" => address.into()
+            },
+        );
+    }
+
+    pub fn revoke_role<A: Into<Key>>(&mut self, role: &str, address: A, sender: Sender) {
+        self.call(
+            sender,
+            "revoke_role",
+            runtime_args! {
+                "role" => role.to_string(),
+                "address" => address.into()
+            },
+        );
+    }
+
+    pub fn renounce_role<A: Into<Key>>(&mut self, role: &str, address: A, sender: Sender) {
+        self.call(
+            sender,
+            "renounce_role",
+            runtime_args! {
+                "role" => role.to_string(),
+                "address" => address.into()
+            },
+        );
+    }
+
+    pub fn nonces<A: Into<Key>>(&self, owner: A) -> u64 {
+        let nonce_item_key = address_item_key(owner.into());
+
+        let key = Key::Hash(self.contract_hash().value());
+        self.context
+            .query_dictionary_item(key, Some("nonces".to_string()), nonce_item_key)
+            .ok()
+            .map(|value| value.into_t::<u64>().unwrap())
+            .unwrap_or_default()
+    }
+
+    /// Signs a permit off-chain the same way a client would do.
+    pub fn sign_permit<A: Into<Key>>(
+        &self,
+        owner_secret_key: &SecretKey,
+        spender: A,
+        amount: TokenAmount,
+        deadline: u64,
+        nonce: u64,
+    ) -> Vec<u8> {
+        let owner_public_key = PublicKey::from(owner_secret_key);
+
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(b"erc20-permit");
+        preimage.extend_from_slice(
+            &self.named_key_hash(&installer_key(token_cfg::NAME, CONTRACT_PACKAGE_KEY)),
+        );
+        preimage.append(&mut owner_public_key.to_bytes().unwrap());
+        preimage.append(&mut spender.into().to_bytes().unwrap());
+        preimage.append(&mut amount.to_bytes().unwrap());
+        preimage.append(&mut deadline.to_bytes().unwrap());
+        preimage.append(&mut nonce.to_bytes().unwrap());
+        let message = blake2b256(&preimage);
+
+        let signature = crypto::sign(&message, owner_secret_key, &owner_public_key);
+        signature.to_bytes().unwrap()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit<A: Into<Key>>(
+        &mut self,
+        owner_public_key: PublicKey,
+        spender: A,
+        amount: TokenAmount,
+        deadline: u64,
+        nonce: u64,
+        signature: Vec<u8>,
+        sender: Sender,
+    ) {
+        self.call(
+            sender,
+            "permit",
+            runtime_args! {
+                "owner_public_key" => owner_public_key,
+                "spender" => spender.into(),
+                "amount" => amount,
+                "deadline" => deadline,
+                "nonce" => nonce,
+                "signature" => signature
+            },
+        );
+    }
+
+    pub fn owner(&self) -> Option<Key> {
+        self.query_contract("owner").unwrap()
+    }
+
+    pub fn pending_owner(&self) -> Option<Key> {
+        self.query_contract("pending_owner").unwrap()
+    }
+
+    pub fn transfer_ownership<A: Into<Key>>(&mut self, new_owner: A, sender: Sender) {
+        self.call(
+            sender,
+            "transfer_ownership",
+            runtime_args! {
+                "new_owner" => new_owner.into()
+            },
+        );
+    }
+
+    pub fn accept_ownership(&mut self, sender: Sender) {
+        self.call(sender, "accept_ownership", runtime_args! {});
+    }
+
+    pub fn renounce_ownership(&mut self, sender: Sender) {
+        self.call(sender, "renounce_ownership", runtime_args! {});
+    }
+
+    pub fn paused(&self) -> bool {
+        self.query_contract("paused").unwrap()
+    }
+
+    pub fn snapshot(&mut self, sender: Sender) {
+        self.call(sender, "snapshot", runtime_args! {});
+    }
+
+    /// Calls the `balance_of_at` entry point, so its validation of the snapshot id is exercised.
+    pub fn call_balance_of_at<A: Into<Key>>(
+        &mut self,
+        address: A,
+        snapshot_id: u64,
+        sender: Sender,
+    ) {
+        self.call(
+            sender,
+            "balance_of_at",
+            runtime_args! {
+                "address" => address.into(),
+                "snapshot_id" => snapshot_id
+            },
+        );
+    }
+
+    pub fn delegate<A: Into<Key>>(&mut self, delegatee: A, sender: Sender) {
+        self.call(
+            sender,
+            "delegate",
+            runtime_args! {
+                "delegatee" => delegatee.into()
+            },
+        );
+    }
+
+    /// Calls the `get_past_votes` entry point, so its validation of the timestamp is exercised.
+    pub fn call_get_past_votes<A: Into<Key>>(
+        &mut self,
+        address: A,
+        timestamp: u64,
+        sender: Sender,
+    ) {
+        self.call(
+            sender,
+            "get_past_votes",
+            runtime_args! {
+                "address" => address.into(),
+                "timestamp" => timestamp
+            },
+        );
+    }
+
+    /// Returns the amount reported to the last `on_transfer_received` call of the test contract.
+    pub fn received_amount(&self) -> TokenAmount {
+        self.context
+            .query(
+                self.ali,
+                &[TEST_CONTRACT_KEY.to_string(), "received_amount".to_string()],
+            )
+            .unwrap()
+            .into_t()
+            .unwrap()
+    }
+
+    /// Reads the voting power of an address at a `timestamp` through the `get_past_votes` entry point.
+    pub fn past_votes<A: Into<Key>>(&mut self, address: A, timestamp: u64) -> TokenAmount {
+        let token_contract = self.contract_hash();
+        self.call_test_contract(
+            Sender(self.ali),
+            "store_past_votes",
+            runtime_args! {
+                "token_contract" => token_contract,
+                "address" => address.into(),
+                "timestamp" => timestamp
+            },
+        );
+
+        self.context
+            .query(
+                self.ali,
+                &[TEST_CONTRACT_KEY.to_string(), "past_votes".to_string()],
+            )
+            .unwrap()
+            .into_t()
+            .unwrap()
+    }
+
+    pub fn is_frozen<A: Into<Key>>(&self, address: A) -> bool {
+        let frozen_item_key = address_item_key(address.into());
+
+        let key = Key::Hash(self.contract_hash().value());
+        self.context
+            .query_dictionary_item(key, Some("frozen_accounts".to_string()), frozen_item_key)
+            .map(|value| value.into_t::<bool>().unwrap())
+            .unwrap_or_default()
+    }
+
+    pub fn freeze<A: Into<Key>>(&mut self, address: A, sender: Sender) {
+        self.call(
+            sender,
+            "freeze",
+            runtime_args! {
+                "address" => address.into()
+            },
+        );
+    }
+
+    pub fn unfreeze<A: Into<Key>>(&mut self, address: A, sender: Sender) {
+        self.call(
+            sender,
+            "unfreeze",
+            runtime_args! {
+                "address" => address.into()
+            },
+        );
+    }
+
+    pub fn fee_basis_points(&self) -> u32 {
+        self.query_contract("fee_basis_points").unwrap()
+    }
+
+    pub fn fee_recipient(&self) -> Option<Key> {
+        self.query_contract("fee_recipient").unwrap()
+    }
+
+    pub fn set_transfer_fee<A: Into<Key>>(
+        &mut self,
+        fee_basis_points: u32,
+        fee_recipient: A,
+        sender: Sender,
+    ) {
+        self.call(
+            sender,
+            "set_transfer_fee",
+            runtime_args! {
+                "fee_basis_points" => fee_basis_points,
+                "fee_recipient" => fee_recipient.into()
+            },
+        );
+    }
+
+    pub fn set_fee_exempt<A: Into<Key>>(&mut self, address: A, exempt: bool, sender: Sender) {
+        self.call(
+            sender,
+            "set_fee_exempt",
+            runtime_args! {
+                "address" => address.into(),
+                "exempt" => exempt
+            },
+        );
+    }
+
+    pub fn vesting_schedule<A: Into<Key>>(&self, beneficiary: A) -> Option<VestingSchedule> {
+        let vesting_item_key = address_item_key(beneficiary.into());
+
+        let key = Key::Hash(self.contract_hash().value());
+        let value = self
+            .context
+            .query_dictionary_item(key, Some("vesting_schedules".to_string()), vesting_item_key)
+            .ok()?;
+
+        Some(value.into_t::<VestingSchedule>().unwrap())
+    }
+
+    pub fn create_vesting_schedule<A: Into<Key>>(
+        &mut self,
+        beneficiary: A,
+        amount: TokenAmount,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+        sender: Sender,
+    ) {
+        self.call(
+            sender,
+            "create_vesting_schedule",
+            runtime_args! {
+                "beneficiary" => beneficiary.into(),
+                "amount" => amount,
+                "start" => start,
+                "cliff" => cliff,
+                "duration" => duration
+            },
+        );
+    }
+
+    pub fn release(&mut self, sender: Sender) {
+        self.call(sender, "release", runtime_args! {});
+    }
+
+    fn test_contract_package_hash(&self) -> ContractPackageHash {
+        ContractPackageHash::new(self.named_key_hash(TEST_CONTRACT_PACKAGE_KEY))
+    }
+
+    /// Transfers tokens to the test contract, which handles them according to the first byte of `data`.
+    pub fn transfer_and_call(&mut self, amount: TokenAmount, data: Vec<u8>, sender: Sender) {
+        let recipient = self.test_contract_package_hash();
+        self.call(
+            sender,
+            "transfer_and_call",
+            runtime_args! {
                 "recipient" => recipient,
+                "amount" => amount,
+                "data" => data
+            },
+        );
+    }
+
+    /// Approves tokens to the test contract, which handles them according to the first byte of `data`.
+    pub fn approve_and_call(&mut self, amount: TokenAmount, data: Vec<u8>, sender: Sender) {
+        let spender = self.test_contract_package_hash();
+        self.call(
+            sender,
+            "approve_and_call",
+            runtime_args! {
+                "spender" => spender,
+                "amount" => amount,
+                "data" => data
+            },
+        );
+    }
+
+    /// Requests a flash loan to the test contract, which handles it according to the first byte of `data`.
+    pub fn flash_loan(&mut self, amount: TokenAmount, data: Vec<u8>, sender: Sender) {
+        let receiver = self.test_contract_package_hash();
+        self.call(
+            sender,
+            "flash_loan",
+            runtime_args! {
+                "receiver" => receiver,
+                "amount" => amount,
+                "data" => data
+            },
+        );
+    }
+
+    /// Deposits CSPR through a session which funds a new purse, so the token never gets access to the main purse of the sender.
+    pub fn deposit(&mut self, amount: TokenAmount, sender: Sender) {
+        let Sender(address) = sender;
+        let session_code = Code::from("deposit_session.wasm");
+        let session_args = runtime_args! {
+            "token_contract" => self.contract_hash(),
+            "amount" => amount
+        };
+        let session = SessionBuilder::new(session_code, session_args)
+            .with_address(address)
+            .with_authorization_keys(&[address])
+            .with_block_time(self.block_time)
+            .build();
+        self.context.run(session);
+    }
+
+    pub fn withdraw(&mut self, amount: TokenAmount, sender: Sender) {
+        self.call(
+            sender,
+            "withdraw",
+            runtime_args! {
+                "amount" => amount
+            },
+        );
+    }
+
+    pub fn pause(&mut self, sender: Sender) {
+        self.call(sender, "pause", runtime_args! {});
+    }
+
+    pub fn unpause(&mut self, sender: Sender) {
+        self.call(sender, "unpause", runtime_args! {});
+    }
+
+    pub fn mint<A: Into<Key>>(&mut self, owner: A, amount: TokenAmount, sender: Sender) {
+        self.call(
+            sender,
+            "mint",
+            runtime_args! {
+                "owner" => owner.into(),
+                "amount" => amount
+            },
+        );
+    }
+
+    pub fn burn<A: Into<Key>>(&mut self, owner: A, amount: TokenAmount, sender: Sender) {
+        self.call(
+            sender,
+            "burn",
+            runtime_args! {
+                "owner" => owner.into(),
+                "amount" => amount
+            },
+        );
+    }
+
+    pub fn transfer_as_stored_contract<A: Into<Key>>(
+        &mut self,
+        recipient: A,
+        amount: TokenAmount,
+        sender: Sender,
+    ) {
+        let token_contract = self.contract_hash();
+        self.call_test_contract(
+            sender,
+            "transfer_as_stored_contract",
+            runtime_args! {
+                "token_contract" => token_contract,
+                "recipient" => recipient.into(),
+                "amount" => amount
+            },
+        );
+    }
+
+    pub fn approve_as_stored_contract<A: Into<Key>>(
+        &mut self,
+        spender: A,
+        amount: TokenAmount,
+        sender: Sender,
+    ) {
+        let token_contract = self.contract_hash();
+        self.call_test_contract(
+            sender,
+            "approve_as_stored_contract",
+            runtime_args! {
+                "token_contract" => token_contract,
+                "spender" => spender.into(),
+                "amount" => amount
+            },
+        );
+    }
+
+    pub fn transfer_from_as_stored_contract<A: Into<Key>, B: Into<Key>>(
+        &mut self,
+        owner: A,
+        recipient: B,
+        amount: TokenAmount,
+        sender: Sender,
+    ) {
+        let token_contract = self.contract_hash();
+        self.call_test_contract(
+            sender,
+            "transfer_from_as_stored_contract",
+            runtime_args! {
+                "token_contract" => token_contract,
+                "owner" => owner.into(),
+                "recipient" => recipient.into(),
                 "amount" => amount
             },
         );
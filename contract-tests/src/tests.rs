@@ -1,6 +1,8 @@
-use casper_types::U512;
+use casper_types::{account::AccountHash, Key, PublicKey, SecretKey};
 
-use crate::erc20::{token_cfg, Sender, Token};
+use crate::erc20::{
+    roles, token_cfg, Allowance, Event, Sender, Token, TokenAmount, VestingSchedule,
+};
 
 #[test]
 fn test_erc20_deploy() {
@@ -8,13 +10,14 @@ fn test_erc20_deploy() {
     assert_eq!(t.name(), token_cfg::NAME);
     assert_eq!(t.symbol(), token_cfg::SYMBOL);
     assert_eq!(t.decimals(), token_cfg::DECIMALS);
+    assert_eq!(t.total_supply(), token_cfg::total_supply());
     assert_eq!(t.balance_of(t.ali), Some(token_cfg::total_supply()));
 }
 
 #[test]
 fn test_erc20_transfer() {
-    let transfer_amount_1 = U512::from(42);
-    let transfer_amount_2 = U512::from(20);
+    let transfer_amount_1 = TokenAmount::from(42);
+    let transfer_amount_2 = TokenAmount::from(20);
 
     let mut t = Token::deploy();
     // ali -> bob
@@ -50,11 +53,11 @@ fn should_transfer_full_amount() {
     t.transfer(t.bob, initial_ali_balance, Sender(t.ali));
 
     assert_eq!(t.balance_of(t.bob), Some(initial_ali_balance));
-    assert_eq!(t.balance_of(t.ali), Some(U512::zero()));
+    assert_eq!(t.balance_of(t.ali), Some(TokenAmount::zero()));
 
     t.transfer(t.ali, initial_ali_balance, Sender(t.bob));
 
-    assert_eq!(t.balance_of(t.bob), Some(U512::zero()));
+    assert_eq!(t.balance_of(t.bob), Some(TokenAmount::zero()));
     assert_eq!(t.balance_of(t.ali), Some(initial_ali_balance));
 }
 
@@ -66,14 +69,18 @@ fn should_not_transfer_with_insufficient_balance() {
     let initial_ali_balance = t.balance_of(t.ali).unwrap();
     assert_eq!(t.balance_of(t.bob), None);
 
-    t.transfer(t.bob, initial_ali_balance + U512::one(), Sender(t.ali));
+    t.transfer(
+        t.bob,
+        initial_ali_balance + TokenAmount::one(),
+        Sender(t.ali),
+    );
 }
 
 #[test]
 fn test_erc20_transfer_from() {
     // NOTE: exercises the happy path
-    let approve_amount = U512::from(100);
-    let transfer_amount = U512::from(42);
+    let approve_amount = TokenAmount::from(100);
+    let transfer_amount = TokenAmount::from(42);
     assert!(approve_amount > transfer_amount);
 
     let mut t = Token::deploy();
@@ -109,8 +116,8 @@ fn test_erc20_transfer_from() {
 #[test]
 fn test_should_not_transfer_from_more_than_approved() {
     // NOTE: exercises the happy path
-    let approve_amount = U512::from(100);
-    let transfer_amount = U512::from(42);
+    let approve_amount = TokenAmount::from(100);
+    let transfer_amount = TokenAmount::from(42);
     assert!(approve_amount > transfer_amount);
 
     let mut t = Token::deploy();
@@ -126,7 +133,7 @@ fn test_should_not_transfer_from_more_than_approved() {
     t.transfer_from(
         owner,
         recipient,
-        approve_amount + U512::one(),
+        approve_amount + TokenAmount::one(),
         Sender(spender),
     );
 
@@ -146,3 +153,1502 @@ fn test_should_not_transfer_from_more_than_approved() {
         "recipient should receive tokens"
     );
 }
+
+#[test]
+fn should_transfer_to_contract_package_address() {
+    let transfer_amount = TokenAmount::from(42);
+
+    let mut t = Token::deploy();
+
+    let contract_address = Key::Hash([42; 32]);
+    let account_address = Key::Account(AccountHash::new([42; 32]));
+
+    assert_eq!(t.balance_of(contract_address), None);
+    assert_eq!(t.balance_of(account_address), None);
+
+    t.transfer(contract_address, transfer_amount, Sender(t.ali));
+
+    assert_eq!(t.balance_of(contract_address), Some(transfer_amount));
+    assert_eq!(
+        t.balance_of(account_address),
+        None,
+        "account with the same hash should not share a balance with the contract"
+    );
+    assert_eq!(
+        t.balance_of(t.ali),
+        Some(token_cfg::total_supply() - transfer_amount)
+    );
+}
+
+#[test]
+fn should_transfer_as_stored_contract() {
+    let deposit_amount = TokenAmount::from(100);
+    let transfer_amount = TokenAmount::from(42);
+
+    let mut t = Token::deploy();
+    let test_contract = t.test_contract();
+
+    t.transfer(test_contract, deposit_amount, Sender(t.ali));
+    assert_eq!(t.balance_of(test_contract), Some(deposit_amount));
+
+    t.transfer_as_stored_contract(t.joe, transfer_amount, Sender(t.bob));
+
+    assert_eq!(
+        t.balance_of(test_contract),
+        Some(deposit_amount - transfer_amount),
+        "should decrease balance of the stored contract"
+    );
+    assert_eq!(t.balance_of(t.joe), Some(transfer_amount));
+    assert_eq!(
+        t.balance_of(t.bob),
+        None,
+        "caller of the test contract should not be charged"
+    );
+}
+
+#[should_panic(expected = "ApiError::User(1) [65537]")]
+#[test]
+fn should_not_transfer_as_stored_contract_with_insufficient_balance() {
+    let mut t = Token::deploy();
+
+    t.transfer_as_stored_contract(t.joe, TokenAmount::one(), Sender(t.ali));
+}
+
+#[test]
+fn should_transfer_from_as_stored_contract() {
+    let approve_amount = TokenAmount::from(100);
+    let transfer_amount = TokenAmount::from(42);
+
+    let mut t = Token::deploy();
+    let owner = t.ali;
+    let spender = t.test_contract();
+    let recipient = t.joe;
+
+    let owner_balance_before = t.balance_of(owner).expect("owner should have balance");
+    t.approve(spender, approve_amount, Sender(owner));
+    assert_eq!(t.allowance(owner, spender), Some(approve_amount));
+
+    t.transfer_from_as_stored_contract(owner, recipient, transfer_amount, Sender(t.bob));
+
+    assert_eq!(
+        t.balance_of(owner),
+        Some(owner_balance_before - transfer_amount),
+        "should decrease balance of the owner"
+    );
+    assert_eq!(
+        t.allowance(owner, spender),
+        Some(approve_amount - transfer_amount),
+        "should decrease allowance of the stored contract"
+    );
+    assert_eq!(t.balance_of(recipient), Some(transfer_amount));
+}
+
+#[test]
+fn should_approve_as_stored_contract() {
+    let deposit_amount = TokenAmount::from(100);
+    let approve_amount = TokenAmount::from(50);
+    let transfer_amount = TokenAmount::from(42);
+
+    let mut t = Token::deploy();
+    let owner = t.test_contract();
+    let spender = t.bob;
+    let recipient = t.joe;
+
+    t.transfer(owner, deposit_amount, Sender(t.ali));
+
+    t.approve_as_stored_contract(spender, approve_amount, Sender(t.ali));
+    assert_eq!(t.allowance(owner, spender), Some(approve_amount));
+
+    t.transfer_from(owner, recipient, transfer_amount, Sender(spender));
+
+    assert_eq!(
+        t.balance_of(owner),
+        Some(deposit_amount - transfer_amount),
+        "should decrease balance of the stored contract"
+    );
+    assert_eq!(
+        t.allowance(owner, spender),
+        Some(approve_amount - transfer_amount)
+    );
+    assert_eq!(t.balance_of(recipient), Some(transfer_amount));
+}
+
+#[test]
+fn should_emit_transfer_event_for_initial_supply() {
+    let t = Token::deploy();
+
+    assert_eq!(
+        t.events(),
+        vec![Event::Transfer {
+            from: None,
+            to: Some(t.ali.into()),
+            amount: token_cfg::total_supply(),
+        }]
+    );
+}
+
+#[test]
+fn should_emit_transfer_and_approval_events() {
+    let approve_amount = TokenAmount::from(100);
+    let transfer_amount = TokenAmount::from(42);
+    let transfer_from_amount = TokenAmount::from(20);
+
+    let mut t = Token::deploy();
+
+    t.transfer(t.bob, transfer_amount, Sender(t.ali));
+    t.approve(t.bob, approve_amount, Sender(t.ali));
+    t.transfer_from(t.ali, t.joe, transfer_from_amount, Sender(t.bob));
+
+    let events = t.events();
+    assert_eq!(events.len(), 4);
+    assert_eq!(
+        events[1..],
+        [
+            Event::Transfer {
+                from: Some(t.ali.into()),
+                to: Some(t.bob.into()),
+                amount: transfer_amount,
+            },
+            Event::Approval {
+                owner: t.ali.into(),
+                spender: t.bob.into(),
+                amount: approve_amount,
+            },
+            Event::Transfer {
+                from: Some(t.ali.into()),
+                to: Some(t.joe.into()),
+                amount: transfer_from_amount,
+            },
+        ]
+    );
+}
+
+#[test]
+fn should_keep_total_supply_equal_to_sum_of_balances() {
+    let mut t = Token::deploy();
+    let test_contract = t.test_contract();
+
+    t.transfer(t.bob, TokenAmount::from(100), Sender(t.ali));
+    t.mint(t.joe, TokenAmount::from(500), Sender(t.ali));
+    t.transfer(test_contract, TokenAmount::from(50), Sender(t.bob));
+    t.transfer(t.bob, TokenAmount::from(25), Sender(t.bob));
+    t.burn(t.ali, TokenAmount::from(200), Sender(t.ali));
+    t.transfer_as_stored_contract(t.joe, TokenAmount::from(20), Sender(t.ali));
+    t.mint(test_contract, TokenAmount::from(10), Sender(t.ali));
+    t.approve(t.bob, TokenAmount::from(30), Sender(t.joe));
+    t.transfer_from(t.joe, t.ali, TokenAmount::from(30), Sender(t.bob));
+    t.burn(t.joe, TokenAmount::from(40), Sender(t.ali));
+
+    let holders = [t.ali.into(), t.bob.into(), t.joe.into(), test_contract];
+    let sum_of_balances = holders
+        .iter()
+        .map(|holder| t.balance_of(*holder).unwrap_or_default())
+        .fold(TokenAmount::zero(), |acc, balance| acc + balance);
+
+    assert_eq!(
+        t.total_supply(),
+        token_cfg::total_supply() + TokenAmount::from(500) - TokenAmount::from(200)
+            + TokenAmount::from(10)
+            - TokenAmount::from(40)
+    );
+    assert_eq!(sum_of_balances, t.total_supply());
+}
+
+#[test]
+fn should_mint_and_burn_as_minter() {
+    let mint_amount = TokenAmount::from(100);
+    let burn_amount = TokenAmount::from(42);
+
+    let mut t = Token::deploy();
+
+    t.mint(t.bob, mint_amount, Sender(t.ali));
+    assert_eq!(t.balance_of(t.bob), Some(mint_amount));
+    assert_eq!(t.total_supply(), token_cfg::total_supply() + mint_amount);
+
+    t.burn(t.bob, burn_amount, Sender(t.ali));
+    assert_eq!(t.balance_of(t.bob), Some(mint_amount - burn_amount));
+    assert_eq!(
+        t.total_supply(),
+        token_cfg::total_supply() + mint_amount - burn_amount
+    );
+
+    assert_eq!(
+        t.events()[1..],
+        [
+            Event::Transfer {
+                from: None,
+                to: Some(t.bob.into()),
+                amount: mint_amount,
+            },
+            Event::Transfer {
+                from: Some(t.bob.into()),
+                to: None,
+                amount: burn_amount,
+            },
+        ]
+    );
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_mint_as_non_minter() {
+    let mut t = Token::deploy();
+
+    t.mint(t.bob, TokenAmount::one(), Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_burn_as_non_minter() {
+    let mut t = Token::deploy();
+
+    t.burn(t.ali, TokenAmount::one(), Sender(t.bob));
+}
+
+#[should_panic(expected = "NoSuchMethod")]
+#[test]
+fn should_not_mint_if_not_mintable() {
+    let mut t = Token::deploy_with(false, token_cfg::cap());
+
+    t.mint(t.bob, TokenAmount::one(), Sender(t.ali));
+}
+
+#[test]
+fn should_increase_and_decrease_allowance() {
+    let initial_amount = TokenAmount::from(100);
+    let increase_amount = TokenAmount::from(50);
+    let decrease_amount = TokenAmount::from(30);
+
+    let mut t = Token::deploy();
+    let owner = t.ali;
+    let spender = t.bob;
+
+    t.increase_allowance(spender, initial_amount, Sender(owner));
+    assert_eq!(t.allowance(owner, spender), Some(initial_amount));
+
+    t.increase_allowance(spender, increase_amount, Sender(owner));
+    assert_eq!(
+        t.allowance(owner, spender),
+        Some(initial_amount + increase_amount)
+    );
+
+    t.decrease_allowance(spender, decrease_amount, Sender(owner));
+    assert_eq!(
+        t.allowance(owner, spender),
+        Some(initial_amount + increase_amount - decrease_amount)
+    );
+
+    assert_eq!(
+        t.events().last(),
+        Some(&Event::Approval {
+            owner: owner.into(),
+            spender: spender.into(),
+            amount: initial_amount + increase_amount - decrease_amount,
+        })
+    );
+}
+
+#[should_panic(expected = "ApiError::User(3) [65539]")]
+#[test]
+fn should_not_increase_allowance_above_max() {
+    let mut t = Token::deploy();
+
+    t.approve(t.bob, TokenAmount::max_value(), Sender(t.ali));
+    t.increase_allowance(t.bob, TokenAmount::one(), Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(5) [65541]")]
+#[test]
+fn should_not_decrease_allowance_below_zero() {
+    let mut t = Token::deploy();
+
+    t.approve(t.bob, TokenAmount::from(10), Sender(t.ali));
+    t.decrease_allowance(t.bob, TokenAmount::from(11), Sender(t.ali));
+}
+
+#[test]
+fn should_install_another_token_without_upgrading_the_first() {
+    let mut t = Token::deploy();
+    let contract_hash_before = t.contract_hash();
+
+    t.install_another("Other", "OTH");
+
+    assert_eq!(t.contract_hash(), contract_hash_before);
+    assert_eq!(t.name(), token_cfg::NAME);
+    assert_eq!(t.symbol(), token_cfg::SYMBOL);
+    assert_eq!(t.symbol_of("Other"), "OTH");
+}
+
+#[should_panic(expected = "ApiError::User(23) [65559]")]
+#[test]
+fn should_not_install_token_with_the_same_name_twice() {
+    let mut t = Token::deploy();
+    t.install_another(token_cfg::NAME, "OTH");
+}
+
+#[test]
+fn should_preserve_state_after_upgrade() {
+    let transfer_amount = TokenAmount::from(42);
+    let approve_amount = TokenAmount::from(100);
+
+    let mut t = Token::deploy();
+    let contract_package = t.contract_package();
+
+    t.transfer(t.bob, transfer_amount, Sender(t.ali));
+    t.approve(t.joe, approve_amount, Sender(t.ali));
+
+    let contract_hash_before = t.contract_hash();
+    t.upgrade(token_cfg::MINTABLE);
+
+    assert_ne!(
+        t.contract_hash(),
+        contract_hash_before,
+        "should install a new contract version"
+    );
+    assert_eq!(t.contract_package(), contract_package);
+
+    assert_eq!(t.name(), token_cfg::NAME);
+    assert_eq!(t.symbol(), token_cfg::SYMBOL);
+    assert_eq!(t.decimals(), token_cfg::DECIMALS);
+    assert_eq!(t.total_supply(), token_cfg::total_supply());
+    assert_eq!(t.balance_of(t.bob), Some(transfer_amount));
+    assert_eq!(
+        t.balance_of(t.ali),
+        Some(token_cfg::total_supply() - transfer_amount)
+    );
+    assert_eq!(t.allowance(t.ali, t.joe), Some(approve_amount));
+
+    t.transfer(t.ali, transfer_amount, Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), Some(TokenAmount::zero()));
+    assert_eq!(t.balance_of(t.ali), Some(token_cfg::total_supply()));
+}
+
+#[test]
+fn should_pause_and_unpause_as_admin() {
+    let transfer_amount = TokenAmount::from(42);
+
+    let mut t = Token::deploy();
+    assert!(!t.paused());
+
+    t.pause(Sender(t.ali));
+    assert!(t.paused());
+    assert_eq!(
+        t.balance_of(t.ali),
+        Some(token_cfg::total_supply()),
+        "balances should be readable while paused"
+    );
+
+    t.unpause(Sender(t.ali));
+    assert!(!t.paused());
+
+    t.transfer(t.bob, transfer_amount, Sender(t.ali));
+    assert_eq!(t.balance_of(t.bob), Some(transfer_amount));
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_pause_as_non_admin() {
+    let mut t = Token::deploy();
+
+    t.pause(Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(6) [65542]")]
+#[test]
+fn should_not_transfer_while_paused() {
+    let mut t = Token::deploy();
+
+    t.pause(Sender(t.ali));
+    t.transfer(t.bob, TokenAmount::one(), Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(6) [65542]")]
+#[test]
+fn should_not_transfer_from_while_paused() {
+    let mut t = Token::deploy();
+
+    t.approve(t.bob, TokenAmount::from(10), Sender(t.ali));
+    t.pause(Sender(t.ali));
+    t.transfer_from(t.ali, t.joe, TokenAmount::one(), Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(6) [65542]")]
+#[test]
+fn should_not_approve_while_paused() {
+    let mut t = Token::deploy();
+
+    t.pause(Sender(t.ali));
+    t.approve(t.bob, TokenAmount::one(), Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(6) [65542]")]
+#[test]
+fn should_not_mint_while_paused() {
+    let mut t = Token::deploy();
+
+    t.pause(Sender(t.ali));
+    t.mint(t.bob, TokenAmount::one(), Sender(t.ali));
+}
+
+#[test]
+fn should_grant_all_roles_to_installer() {
+    let t = Token::deploy();
+
+    assert!(t.has_role(roles::ADMIN, t.ali));
+    assert!(t.has_role(roles::MINTER, t.ali));
+    assert!(t.has_role(roles::PAUSER, t.ali));
+
+    assert!(!t.has_role(roles::ADMIN, t.bob));
+    assert!(!t.has_role(roles::MINTER, t.bob));
+    assert!(!t.has_role(roles::PAUSER, t.bob));
+}
+
+#[test]
+fn should_not_grant_minter_role_if_not_mintable() {
+    let t = Token::deploy_with(false, token_cfg::cap());
+
+    assert!(t.has_role(roles::ADMIN, t.ali));
+    assert!(!t.has_role(roles::MINTER, t.ali));
+}
+
+#[test]
+fn should_grant_and_revoke_role_as_admin() {
+    let mint_amount = TokenAmount::from(42);
+
+    let mut t = Token::deploy();
+
+    t.grant_role(roles::MINTER, t.bob, Sender(t.ali));
+    assert!(t.has_role(roles::MINTER, t.bob));
+
+    t.mint(t.joe, mint_amount, Sender(t.bob));
+    assert_eq!(t.balance_of(t.joe), Some(mint_amount));
+
+    t.revoke_role(roles::MINTER, t.bob, Sender(t.ali));
+    assert!(!t.has_role(roles::MINTER, t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_mint_after_role_is_revoked() {
+    let mut t = Token::deploy();
+
+    t.grant_role(roles::MINTER, t.bob, Sender(t.ali));
+    t.revoke_role(roles::MINTER, t.bob, Sender(t.ali));
+
+    t.mint(t.joe, TokenAmount::one(), Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_grant_role_as_non_admin() {
+    let mut t = Token::deploy();
+
+    t.grant_role(roles::MINTER, t.bob, Sender(t.bob));
+}
+
+#[test]
+fn should_renounce_own_role() {
+    let mut t = Token::deploy();
+
+    t.renounce_role(roles::PAUSER, t.ali, Sender(t.ali));
+    assert!(!t.has_role(roles::PAUSER, t.ali));
+    assert!(t.has_role(roles::ADMIN, t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_renounce_role_of_other_address() {
+    let mut t = Token::deploy();
+
+    t.renounce_role(roles::PAUSER, t.ali, Sender(t.bob));
+}
+
+#[test]
+fn should_transfer_ownership_in_two_steps() {
+    let mut t = Token::deploy();
+    assert_eq!(t.owner(), Some(t.ali.into()));
+    assert_eq!(t.pending_owner(), None);
+
+    t.transfer_ownership(t.bob, Sender(t.ali));
+    assert_eq!(
+        t.owner(),
+        Some(t.ali.into()),
+        "owner should not change until the ownership is accepted"
+    );
+    assert_eq!(t.pending_owner(), Some(t.bob.into()));
+
+    t.accept_ownership(Sender(t.bob));
+    assert_eq!(t.owner(), Some(t.bob.into()));
+    assert_eq!(t.pending_owner(), None);
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_transfer_ownership_as_non_owner() {
+    let mut t = Token::deploy();
+
+    t.transfer_ownership(t.bob, Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_accept_ownership_as_non_pending_owner() {
+    let mut t = Token::deploy();
+
+    t.transfer_ownership(t.bob, Sender(t.ali));
+    t.accept_ownership(Sender(t.joe));
+}
+
+#[test]
+fn should_renounce_ownership() {
+    let mut t = Token::deploy();
+
+    t.transfer_ownership(t.bob, Sender(t.ali));
+    t.renounce_ownership(Sender(t.ali));
+
+    assert_eq!(t.owner(), None);
+    assert_eq!(
+        t.pending_owner(),
+        None,
+        "should cancel pending ownership transfer"
+    );
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_transfer_ownership_after_renounce() {
+    let mut t = Token::deploy();
+
+    t.renounce_ownership(Sender(t.ali));
+    t.transfer_ownership(t.bob, Sender(t.ali));
+}
+
+#[test]
+fn should_mint_up_to_cap() {
+    let cap = token_cfg::total_supply() + TokenAmount::from(100);
+
+    let mut t = Token::deploy_with(true, Some(cap));
+    assert_eq!(t.cap(), Some(cap));
+
+    t.mint(t.bob, TokenAmount::from(100), Sender(t.ali));
+
+    assert_eq!(t.balance_of(t.bob), Some(TokenAmount::from(100)));
+    assert_eq!(t.total_supply(), cap);
+}
+
+#[should_panic(expected = "ApiError::User(7) [65543]")]
+#[test]
+fn should_not_mint_past_cap() {
+    let cap = token_cfg::total_supply() + TokenAmount::from(100);
+
+    let mut t = Token::deploy_with(true, Some(cap));
+
+    t.mint(t.bob, TokenAmount::from(100), Sender(t.ali));
+    t.mint(t.bob, TokenAmount::one(), Sender(t.ali));
+}
+
+#[test]
+fn should_mint_again_after_burn_below_cap() {
+    let cap = token_cfg::total_supply();
+
+    let mut t = Token::deploy_with(true, Some(cap));
+
+    t.burn(t.ali, TokenAmount::from(10), Sender(t.ali));
+    t.mint(t.bob, TokenAmount::from(10), Sender(t.ali));
+
+    assert_eq!(t.total_supply(), cap);
+}
+
+#[should_panic(expected = "ApiError::User(7) [65543]")]
+#[test]
+fn should_not_install_with_initial_supply_above_cap() {
+    Token::deploy_with(true, Some(token_cfg::total_supply() - TokenAmount::one()));
+}
+
+fn permit_and_spend(owner_secret_key: SecretKey) {
+    let approve_amount = TokenAmount::from(100);
+    let transfer_amount = TokenAmount::from(42);
+
+    let mut t = Token::deploy();
+    let owner_public_key = PublicKey::from(&owner_secret_key);
+    let owner = owner_public_key.to_account_hash();
+    let spender = t.bob;
+
+    t.transfer(owner, approve_amount, Sender(t.ali));
+    assert_eq!(t.nonces(owner), 0);
+
+    let signature = t.sign_permit(&owner_secret_key, spender, approve_amount, u64::MAX, 0);
+    t.permit(
+        owner_public_key,
+        spender,
+        approve_amount,
+        u64::MAX,
+        0,
+        signature,
+        Sender(spender),
+    );
+
+    assert_eq!(t.allowance(owner, spender), Some(approve_amount));
+    assert_eq!(t.nonces(owner), 1);
+
+    t.transfer_from(owner, t.joe, transfer_amount, Sender(spender));
+    assert_eq!(t.balance_of(t.joe), Some(transfer_amount));
+    assert_eq!(
+        t.allowance(owner, spender),
+        Some(approve_amount - transfer_amount)
+    );
+}
+
+#[test]
+fn should_permit_with_ed25519_signature() {
+    permit_and_spend(SecretKey::ed25519_from_bytes([11u8; 32]).unwrap());
+}
+
+#[test]
+fn should_permit_with_secp256k1_signature() {
+    permit_and_spend(SecretKey::secp256k1_from_bytes([12u8; 32]).unwrap());
+}
+
+#[should_panic(expected = "ApiError::User(9) [65545]")]
+#[test]
+fn should_not_replay_permit() {
+    let mut t = Token::deploy();
+    let owner_secret_key = SecretKey::ed25519_from_bytes([11u8; 32]).unwrap();
+    let owner_public_key = PublicKey::from(&owner_secret_key);
+
+    let signature = t.sign_permit(&owner_secret_key, t.bob, TokenAmount::one(), u64::MAX, 0);
+    t.permit(
+        owner_public_key.clone(),
+        t.bob,
+        TokenAmount::one(),
+        u64::MAX,
+        0,
+        signature.clone(),
+        Sender(t.bob),
+    );
+    t.permit(
+        owner_public_key,
+        t.bob,
+        TokenAmount::one(),
+        u64::MAX,
+        0,
+        signature,
+        Sender(t.bob),
+    );
+}
+
+#[should_panic(expected = "ApiError::User(10) [65546]")]
+#[test]
+fn should_not_permit_with_signature_of_other_key() {
+    let mut t = Token::deploy();
+    let owner_secret_key = SecretKey::ed25519_from_bytes([11u8; 32]).unwrap();
+    let other_secret_key = SecretKey::ed25519_from_bytes([13u8; 32]).unwrap();
+
+    let signature = t.sign_permit(&other_secret_key, t.bob, TokenAmount::one(), u64::MAX, 0);
+    t.permit(
+        PublicKey::from(&owner_secret_key),
+        t.bob,
+        TokenAmount::one(),
+        u64::MAX,
+        0,
+        signature,
+        Sender(t.bob),
+    );
+}
+
+#[should_panic(expected = "ApiError::User(10) [65546]")]
+#[test]
+fn should_not_permit_with_tampered_amount() {
+    let mut t = Token::deploy();
+    let owner_secret_key = SecretKey::ed25519_from_bytes([11u8; 32]).unwrap();
+
+    let signature = t.sign_permit(&owner_secret_key, t.bob, TokenAmount::one(), u64::MAX, 0);
+    t.permit(
+        PublicKey::from(&owner_secret_key),
+        t.bob,
+        TokenAmount::max_value(),
+        u64::MAX,
+        0,
+        signature,
+        Sender(t.bob),
+    );
+}
+
+#[test]
+fn should_read_balances_at_snapshot() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+    assert_eq!(t.snapshot_id(), 0);
+
+    t.snapshot(Sender(t.ali));
+    assert_eq!(t.snapshot_id(), 1);
+    assert_eq!(t.events().last(), Some(&Event::Snapshot { id: 1 }));
+
+    t.transfer(t.bob, amount, Sender(t.ali));
+    t.mint(t.joe, amount, Sender(t.ali));
+
+    t.snapshot(Sender(t.ali));
+    t.transfer(t.joe, amount, Sender(t.bob));
+    t.burn(t.joe, amount, Sender(t.ali));
+
+    // First snapshot sees balances from before the transfer and the mint.
+    assert_eq!(t.balance_of_at(t.ali, 1), token_cfg::total_supply());
+    assert_eq!(t.balance_of_at(t.bob, 1), TokenAmount::zero());
+    assert_eq!(t.balance_of_at(t.joe, 1), TokenAmount::zero());
+    assert_eq!(t.total_supply_at(1), token_cfg::total_supply());
+
+    // Second snapshot sees balances from before the second transfer and the burn.
+    assert_eq!(
+        t.balance_of_at(t.ali, 2),
+        token_cfg::total_supply() - amount
+    );
+    assert_eq!(t.balance_of_at(t.bob, 2), amount);
+    assert_eq!(t.balance_of_at(t.joe, 2), amount);
+    assert_eq!(t.total_supply_at(2), token_cfg::total_supply() + amount);
+
+    assert_eq!(t.balance_of(t.bob), Some(TokenAmount::zero()));
+    assert_eq!(t.balance_of(t.joe), Some(amount));
+    assert_eq!(t.total_supply(), token_cfg::total_supply());
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_snapshot_without_admin_role() {
+    let mut t = Token::deploy();
+    t.snapshot(Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(11) [65547]")]
+#[test]
+fn should_not_read_balance_at_future_snapshot() {
+    let mut t = Token::deploy();
+    t.snapshot(Sender(t.ali));
+    t.call_balance_of_at(t.ali, 2, Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(11) [65547]")]
+#[test]
+fn should_not_read_balance_at_snapshot_zero() {
+    let mut t = Token::deploy();
+    t.call_balance_of_at(t.ali, 0, Sender(t.ali));
+}
+
+#[test]
+fn should_delegate_votes() {
+    let mut t = Token::deploy();
+    assert_eq!(t.delegates(t.ali), None);
+    assert_eq!(t.get_votes(t.ali), TokenAmount::zero());
+
+    t.delegate(t.ali, Sender(t.ali));
+    assert_eq!(t.delegates(t.ali), Some(Key::from(t.ali)));
+    assert_eq!(t.get_votes(t.ali), token_cfg::total_supply());
+    assert_eq!(
+        t.events().last(),
+        Some(&Event::DelegateChanged {
+            delegator: t.ali.into(),
+            from_delegate: None,
+            to_delegate: t.ali.into(),
+        })
+    );
+
+    t.delegate(t.joe, Sender(t.ali));
+    assert_eq!(t.get_votes(t.ali), TokenAmount::zero());
+    assert_eq!(t.get_votes(t.joe), token_cfg::total_supply());
+}
+
+#[test]
+fn should_move_votes_with_tokens() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+    t.delegate(t.ali, Sender(t.ali));
+    t.delegate(t.joe, Sender(t.bob));
+
+    t.transfer(t.bob, amount, Sender(t.ali));
+    assert_eq!(t.get_votes(t.ali), token_cfg::total_supply() - amount);
+    assert_eq!(t.get_votes(t.joe), amount);
+
+    t.mint(t.bob, amount, Sender(t.ali));
+    assert_eq!(t.get_votes(t.joe), amount * 2);
+
+    t.burn(t.ali, amount, Sender(t.ali));
+    assert_eq!(t.get_votes(t.ali), token_cfg::total_supply() - amount * 2);
+
+    // Tokens of an address that never delegated don't carry voting power.
+    t.transfer(t.joe, amount, Sender(t.bob));
+    assert_eq!(t.get_votes(t.joe), amount);
+    assert_eq!(t.delegates(t.joe), None);
+}
+
+#[test]
+fn should_get_past_votes() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+
+    t.set_block_time(1_000);
+    t.delegate(t.ali, Sender(t.ali));
+
+    t.set_block_time(2_000);
+    t.transfer(t.bob, amount, Sender(t.ali));
+
+    t.set_block_time(3_000);
+    t.transfer(t.bob, amount, Sender(t.ali));
+
+    t.set_block_time(4_000);
+    assert_eq!(t.past_votes(t.ali, 999), TokenAmount::zero());
+    assert_eq!(t.past_votes(t.ali, 1_000), token_cfg::total_supply());
+    assert_eq!(t.past_votes(t.ali, 1_999), token_cfg::total_supply());
+    assert_eq!(
+        t.past_votes(t.ali, 2_000),
+        token_cfg::total_supply() - amount
+    );
+    assert_eq!(
+        t.past_votes(t.ali, 3_999),
+        token_cfg::total_supply() - amount - amount
+    );
+    assert_eq!(
+        t.get_votes(t.ali),
+        token_cfg::total_supply() - amount - amount
+    );
+}
+
+#[should_panic(expected = "ApiError::User(12) [65548]")]
+#[test]
+fn should_not_get_future_votes() {
+    let mut t = Token::deploy();
+    t.delegate(t.ali, Sender(t.ali));
+    t.call_get_past_votes(t.ali, u64::MAX, Sender(t.ali));
+}
+
+#[test]
+fn should_freeze_and_unfreeze() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+    assert!(!t.is_frozen(t.bob));
+
+    t.freeze(t.bob, Sender(t.ali));
+    assert!(t.is_frozen(t.bob));
+
+    t.unfreeze(t.bob, Sender(t.ali));
+    assert!(!t.is_frozen(t.bob));
+
+    t.transfer(t.bob, amount, Sender(t.ali));
+    assert_eq!(t.balance_of(t.bob), Some(amount));
+}
+
+#[should_panic(expected = "ApiError::User(13) [65549]")]
+#[test]
+fn should_not_transfer_from_frozen_account() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+    t.transfer(t.bob, amount, Sender(t.ali));
+    t.freeze(t.bob, Sender(t.ali));
+    t.transfer(t.joe, amount, Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(13) [65549]")]
+#[test]
+fn should_not_transfer_to_frozen_account() {
+    let mut t = Token::deploy();
+    t.freeze(t.bob, Sender(t.ali));
+    t.transfer(t.bob, TokenAmount::one(), Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(13) [65549]")]
+#[test]
+fn should_not_mint_to_frozen_account() {
+    let mut t = Token::deploy();
+    t.freeze(t.bob, Sender(t.ali));
+    t.mint(t.bob, TokenAmount::one(), Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(13) [65549]")]
+#[test]
+fn should_not_transfer_from_as_frozen_spender() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+    t.approve(t.bob, amount, Sender(t.ali));
+    t.freeze(t.bob, Sender(t.ali));
+    t.transfer_from(t.ali, t.joe, amount, Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_freeze_without_admin_role() {
+    let mut t = Token::deploy();
+    t.freeze(t.joe, Sender(t.bob));
+}
+
+#[test]
+fn should_charge_transfer_fee() {
+    let amount = TokenAmount::from(400);
+    let fee = TokenAmount::from(10);
+    let mut t = Token::deploy();
+    assert_eq!(t.fee_basis_points(), 0);
+    assert_eq!(t.fee_recipient(), None);
+
+    t.set_transfer_fee(250, t.joe, Sender(t.ali));
+    assert_eq!(t.fee_basis_points(), 250);
+    assert_eq!(t.fee_recipient(), Some(Key::from(t.joe)));
+
+    t.transfer(t.bob, amount, Sender(t.ali));
+    assert_eq!(
+        t.balance_of(t.ali),
+        Some(token_cfg::total_supply() - amount)
+    );
+    assert_eq!(t.balance_of(t.bob), Some(amount - fee));
+    assert_eq!(t.balance_of(t.joe), Some(fee));
+    assert_eq!(t.total_supply(), token_cfg::total_supply());
+
+    let events = t.events();
+    assert_eq!(
+        &events[events.len() - 2..],
+        &[
+            Event::Transfer {
+                from: Some(t.ali.into()),
+                to: Some(t.bob.into()),
+                amount: amount - fee,
+            },
+            Event::Transfer {
+                from: Some(t.ali.into()),
+                to: Some(t.joe.into()),
+                amount: fee,
+            },
+        ]
+    );
+}
+
+#[test]
+fn should_charge_transfer_fee_on_transfer_from() {
+    let amount = TokenAmount::from(400);
+    let fee = TokenAmount::from(10);
+    let mut t = Token::deploy();
+    t.set_transfer_fee(250, t.joe, Sender(t.ali));
+
+    t.approve(t.bob, amount, Sender(t.ali));
+    t.transfer_from(t.ali, t.bob, amount, Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), Some(amount - fee));
+    assert_eq!(t.balance_of(t.joe), Some(fee));
+    assert_eq!(t.allowance(t.ali, t.bob), Some(TokenAmount::zero()));
+}
+
+#[test]
+fn should_charge_transfer_fee_on_transfer_to_self() {
+    let amount = TokenAmount::from(400);
+    let fee = TokenAmount::from(10);
+    let mut t = Token::deploy();
+    t.set_transfer_fee(250, t.joe, Sender(t.ali));
+
+    t.transfer(t.ali, amount, Sender(t.ali));
+    assert_eq!(t.balance_of(t.ali), Some(token_cfg::total_supply() - fee));
+    assert_eq!(t.balance_of(t.joe), Some(fee));
+    assert_eq!(t.total_supply(), token_cfg::total_supply());
+}
+
+#[test]
+fn should_round_transfer_fee_down() {
+    let amount = TokenAmount::from(39);
+    let mut t = Token::deploy();
+    t.set_transfer_fee(250, t.joe, Sender(t.ali));
+
+    // 2.5% of 39 is 0.975, which rounds down to no fee at all.
+    t.transfer(t.bob, amount, Sender(t.ali));
+    assert_eq!(t.balance_of(t.bob), Some(amount));
+    assert_eq!(t.balance_of(t.joe), None);
+}
+
+#[test]
+fn should_not_charge_transfer_fee_for_exempt_address() {
+    let amount = TokenAmount::from(400);
+    let mut t = Token::deploy();
+    t.set_transfer_fee(250, t.joe, Sender(t.ali));
+    t.set_fee_exempt(t.bob, true, Sender(t.ali));
+
+    t.transfer(t.bob, amount, Sender(t.ali));
+    assert_eq!(t.balance_of(t.bob), Some(amount));
+
+    t.set_fee_exempt(t.bob, false, Sender(t.ali));
+    t.transfer(t.bob, amount, Sender(t.ali));
+    assert_eq!(t.balance_of(t.bob), Some(amount * 2 - 10));
+}
+
+#[should_panic(expected = "ApiError::User(14) [65550]")]
+#[test]
+fn should_not_set_transfer_fee_above_whole_amount() {
+    let mut t = Token::deploy();
+    t.set_transfer_fee(10_001, t.joe, Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_set_transfer_fee_without_admin_role() {
+    let mut t = Token::deploy();
+    t.set_transfer_fee(250, t.bob, Sender(t.bob));
+}
+
+#[test]
+fn should_lock_tokens_for_vesting() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+    // Locked tokens are moved without a fee.
+    t.set_transfer_fee(250, t.joe, Sender(t.ali));
+
+    t.create_vesting_schedule(t.bob, amount, u64::MAX, 0, 1_000, Sender(t.ali));
+    assert_eq!(
+        t.vesting_schedule(t.bob),
+        Some(VestingSchedule {
+            total: amount,
+            released: TokenAmount::zero(),
+            start: u64::MAX,
+            cliff: 0,
+            duration: 1_000,
+        })
+    );
+    assert_eq!(
+        t.balance_of(t.ali),
+        Some(token_cfg::total_supply() - amount)
+    );
+    assert_eq!(t.balance_of(t.contract_package()), Some(amount));
+    assert_eq!(t.balance_of(t.joe), None);
+
+    // Vesting didn't start yet, so there's nothing to release.
+    t.release(Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), None);
+    assert_eq!(t.balance_of(t.contract_package()), Some(amount));
+}
+
+#[test]
+fn should_keep_charging_fee_to_token_contract_after_vesting() {
+    let amount = TokenAmount::from(400);
+    let fee = TokenAmount::from(10);
+    let mut t = Token::deploy();
+    t.set_transfer_fee(250, t.joe, Sender(t.ali));
+
+    t.create_vesting_schedule(t.bob, TokenAmount::one(), u64::MAX, 0, 1_000, Sender(t.ali));
+
+    // Vesting doesn't exempt the token contract, so other transfers to it still pay the fee.
+    t.transfer(t.contract_package(), amount, Sender(t.ali));
+    assert_eq!(
+        t.balance_of(t.contract_package()),
+        Some(TokenAmount::one() + amount - fee)
+    );
+    assert_eq!(t.balance_of(t.joe), Some(fee));
+}
+
+/// Vesting starts at 10 000 and lasts 4 000, with nothing vested during the first 1 000.
+fn deploy_with_vesting_schedule(amount: TokenAmount) -> Token {
+    let mut t = Token::deploy();
+    t.create_vesting_schedule(t.bob, amount, 10_000, 1_000, 4_000, Sender(t.ali));
+    t
+}
+
+#[test]
+fn should_not_release_before_cliff() {
+    let amount = TokenAmount::from(1_000);
+    let mut t = deploy_with_vesting_schedule(amount);
+
+    t.set_block_time(10_999);
+    t.release(Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), None);
+    assert_eq!(t.balance_of(t.contract_package()), Some(amount));
+}
+
+#[test]
+fn should_release_linearly_after_cliff() {
+    let amount = TokenAmount::from(1_000);
+    let mut t = deploy_with_vesting_schedule(amount);
+
+    // A quarter of the duration has elapsed at the cliff.
+    t.set_block_time(11_000);
+    t.release(Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), Some(TokenAmount::from(250)));
+
+    t.set_block_time(12_500);
+    t.release(Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), Some(TokenAmount::from(625)));
+    assert_eq!(
+        t.balance_of(t.contract_package()),
+        Some(TokenAmount::from(375))
+    );
+    assert_eq!(
+        t.vesting_schedule(t.bob).map(|schedule| schedule.released),
+        Some(TokenAmount::from(625))
+    );
+}
+
+#[test]
+fn should_release_everything_after_end() {
+    let amount = TokenAmount::from(1_000);
+    let mut t = deploy_with_vesting_schedule(amount);
+
+    t.set_block_time(14_000);
+    t.release(Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), Some(amount));
+    assert_eq!(
+        t.balance_of(t.contract_package()),
+        Some(TokenAmount::zero())
+    );
+
+    t.set_block_time(20_000);
+    t.release(Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), Some(amount));
+}
+
+#[should_panic(expected = "ApiError::User(15) [65551]")]
+#[test]
+fn should_not_create_second_vesting_schedule() {
+    let mut t = Token::deploy();
+    t.create_vesting_schedule(t.bob, TokenAmount::one(), 0, 0, 1_000, Sender(t.ali));
+    t.create_vesting_schedule(t.bob, TokenAmount::one(), 0, 0, 1_000, Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(16) [65552]")]
+#[test]
+fn should_not_create_vesting_schedule_with_cliff_after_end() {
+    let mut t = Token::deploy();
+    t.create_vesting_schedule(t.bob, TokenAmount::one(), 0, 1_001, 1_000, Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(17) [65553]")]
+#[test]
+fn should_not_release_without_vesting_schedule() {
+    let mut t = Token::deploy();
+    t.release(Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(4) [65540]")]
+#[test]
+fn should_not_create_vesting_schedule_without_admin_role() {
+    let mut t = Token::deploy();
+    t.transfer(t.bob, TokenAmount::one(), Sender(t.ali));
+    t.create_vesting_schedule(t.joe, TokenAmount::one(), 0, 0, 1_000, Sender(t.bob));
+}
+
+#[test]
+fn should_batch_transfer() {
+    let mut t = Token::deploy();
+    let initial_event_count = t.events().len();
+
+    t.batch_transfer(
+        vec![t.bob.into(), t.joe.into(), t.bob.into()],
+        vec![
+            TokenAmount::from(10),
+            TokenAmount::from(20),
+            TokenAmount::from(30),
+        ],
+        Sender(t.ali),
+    );
+
+    assert_eq!(t.balance_of(t.ali), Some(token_cfg::total_supply() - 60));
+    assert_eq!(t.balance_of(t.bob), Some(TokenAmount::from(40)));
+    assert_eq!(t.balance_of(t.joe), Some(TokenAmount::from(20)));
+    assert_eq!(t.events().len(), initial_event_count + 3);
+}
+
+#[should_panic(expected = "ApiError::User(18) [65554]")]
+#[test]
+fn should_not_batch_transfer_with_length_mismatch() {
+    let mut t = Token::deploy();
+    t.batch_transfer(
+        vec![t.bob.into(), t.joe.into()],
+        vec![TokenAmount::one()],
+        Sender(t.ali),
+    );
+}
+
+#[should_panic(expected = "ApiError::User(1) [65537]")]
+#[test]
+fn should_not_batch_transfer_more_than_balance() {
+    let mut t = Token::deploy();
+    t.batch_transfer(
+        vec![t.bob.into(), t.joe.into()],
+        vec![token_cfg::total_supply(), TokenAmount::one()],
+        Sender(t.ali),
+    );
+}
+
+#[should_panic(expected = "ApiError::User(3) [65539]")]
+#[test]
+fn should_not_batch_transfer_with_overflowing_total() {
+    let mut t = Token::deploy();
+    t.batch_transfer(
+        vec![t.bob.into(), t.joe.into()],
+        vec![TokenAmount::max_value(), TokenAmount::one()],
+        Sender(t.ali),
+    );
+}
+
+/// Test contract accepts and repays a flash loan.
+const FLASH_LOAN_REPAY: u8 = 0;
+/// Test contract rejects a flash loan.
+const FLASH_LOAN_REJECT: u8 = 1;
+/// Test contract sends borrowed tokens to the initiator instead of repaying them.
+const FLASH_LOAN_KEEP: u8 = 2;
+
+#[test]
+fn should_flash_loan() {
+    let amount = TokenAmount::from(1_000_000);
+    let mut t = Token::deploy();
+
+    t.flash_loan(amount, vec![FLASH_LOAN_REPAY], Sender(t.bob));
+
+    assert_eq!(t.total_supply(), token_cfg::total_supply());
+    assert_eq!(t.balance_of(t.test_contract()), Some(TokenAmount::zero()));
+
+    let events = t.events();
+    assert_eq!(
+        &events[events.len() - 2..],
+        &[
+            Event::Transfer {
+                from: None,
+                to: Some(t.test_contract()),
+                amount,
+            },
+            Event::Transfer {
+                from: Some(t.test_contract()),
+                to: None,
+                amount,
+            },
+        ]
+    );
+}
+
+#[should_panic(expected = "ApiError::User(20) [65556]")]
+#[test]
+fn should_not_flash_loan_when_rejected() {
+    let mut t = Token::deploy();
+    t.flash_loan(TokenAmount::one(), vec![FLASH_LOAN_REJECT], Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(1) [65537]")]
+#[test]
+fn should_not_flash_loan_without_repayment() {
+    let mut t = Token::deploy();
+    t.flash_loan(TokenAmount::one(), vec![FLASH_LOAN_KEEP], Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(19) [65555]")]
+#[test]
+fn should_not_flash_loan_above_cap() {
+    let mut t = Token::deploy_with(true, Some(token_cfg::total_supply()));
+    t.flash_loan(TokenAmount::one(), vec![FLASH_LOAN_REPAY], Sender(t.bob));
+}
+
+#[test]
+fn should_deposit_and_withdraw() {
+    let deposit_amount = TokenAmount::from(1_000_000_000u64);
+    let withdraw_amount = TokenAmount::from(400_000_000u64);
+    let mut t = Token::deploy_wrapped();
+    assert_eq!(t.total_supply(), TokenAmount::zero());
+    assert_eq!(t.decimals(), token_cfg::WRAPPED_DECIMALS);
+
+    t.deposit(deposit_amount, Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), Some(deposit_amount));
+    assert_eq!(t.total_supply(), deposit_amount);
+
+    t.withdraw(withdraw_amount, Sender(t.bob));
+    assert_eq!(t.balance_of(t.bob), Some(deposit_amount - withdraw_amount));
+    assert_eq!(t.total_supply(), deposit_amount - withdraw_amount);
+}
+
+#[should_panic(expected = "ApiError::User(1) [65537]")]
+#[test]
+fn should_not_withdraw_more_than_deposited() {
+    let amount = TokenAmount::from(1_000_000_000u64);
+    let mut t = Token::deploy_wrapped();
+    t.deposit(amount, Sender(t.bob));
+    t.withdraw(amount + 1, Sender(t.bob));
+}
+
+#[should_panic(expected = "ApiError::User(1) [65537]")]
+#[test]
+fn should_not_withdraw_deposits_of_others_as_installer() {
+    let amount = TokenAmount::from(1_000_000_000u64);
+    let mut t = Token::deploy_wrapped();
+    t.deposit(amount, Sender(t.bob));
+    t.withdraw(amount, Sender(t.ali));
+}
+
+#[should_panic(expected = "NoSuchMethod")]
+#[test]
+fn should_not_deposit_if_not_wrapped() {
+    let mut t = Token::deploy();
+    t.deposit(TokenAmount::from(1_000_000_000u64), Sender(t.bob));
+}
+
+#[should_panic(expected = "NoSuchMethod")]
+#[test]
+fn should_not_mint_wrapped_token() {
+    let mut t = Token::deploy_wrapped();
+    t.mint(t.ali, TokenAmount::one(), Sender(t.ali));
+}
+
+#[should_panic(expected = "ApiError::User(22) [65558]")]
+#[test]
+fn should_not_install_wrapped_token_with_initial_supply() {
+    Token::deploy_wrapped_with(token_cfg::total_supply(), false);
+}
+
+#[should_panic(expected = "ApiError::User(22) [65558]")]
+#[test]
+fn should_not_install_mintable_wrapped_token() {
+    Token::deploy_wrapped_with(TokenAmount::zero(), true);
+}
+
+#[should_panic(expected = "ApiError::User(22) [65558]")]
+#[test]
+fn should_not_upgrade_wrapped_token_to_mintable() {
+    let mut t = Token::deploy_wrapped();
+    t.upgrade(true);
+}
+
+/// Test contract accepts tokens transferred or approved to it.
+const RECEIVER_ACCEPT: u8 = 0;
+/// Test contract rejects tokens transferred or approved to it.
+const RECEIVER_REJECT: u8 = 1;
+/// Test contract transfers tokens approved to it right away.
+const RECEIVER_PULL: u8 = 2;
+
+#[test]
+fn should_transfer_and_call() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+
+    t.transfer_and_call(amount, vec![RECEIVER_ACCEPT], Sender(t.ali));
+    assert_eq!(t.balance_of(t.test_contract()), Some(amount));
+    assert_eq!(
+        t.balance_of(t.ali),
+        Some(token_cfg::total_supply() - amount)
+    );
+    assert_eq!(t.received_amount(), amount);
+}
+
+#[test]
+fn should_transfer_and_call_with_fee() {
+    let amount = TokenAmount::from(400);
+    let fee = TokenAmount::from(10);
+    let mut t = Token::deploy();
+    t.set_transfer_fee(250, t.joe, Sender(t.ali));
+
+    t.transfer_and_call(amount, vec![RECEIVER_ACCEPT], Sender(t.ali));
+    assert_eq!(t.balance_of(t.test_contract()), Some(amount - fee));
+    assert_eq!(t.balance_of(t.joe), Some(fee));
+    assert_eq!(t.received_amount(), amount - fee);
+}
+
+#[should_panic(expected = "ApiError::User(21) [65557]")]
+#[test]
+fn should_not_transfer_and_call_when_rejected() {
+    let mut t = Token::deploy();
+    t.transfer_and_call(TokenAmount::one(), vec![RECEIVER_REJECT], Sender(t.ali));
+}
+
+#[test]
+fn should_approve_and_call() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+
+    t.approve_and_call(amount, vec![RECEIVER_ACCEPT], Sender(t.ali));
+    assert_eq!(t.allowance(t.ali, t.test_contract()), Some(amount));
+}
+
+#[test]
+fn should_approve_and_call_with_transfer_from_in_hook() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+
+    t.approve_and_call(amount, vec![RECEIVER_PULL], Sender(t.ali));
+    assert_eq!(
+        t.allowance(t.ali, t.test_contract()),
+        Some(TokenAmount::zero())
+    );
+    assert_eq!(t.balance_of(t.test_contract()), Some(amount));
+}
+
+#[should_panic(expected = "ApiError::User(21) [65557]")]
+#[test]
+fn should_not_approve_and_call_when_rejected() {
+    let mut t = Token::deploy();
+    t.approve_and_call(TokenAmount::one(), vec![RECEIVER_REJECT], Sender(t.ali));
+}
+
+#[test]
+fn should_approve_with_expiry() {
+    let amount = TokenAmount::from(100);
+    let transfer_amount = TokenAmount::from(42);
+    let mut t = Token::deploy();
+
+    t.approve_with_expiry(t.bob, amount, u64::MAX, Sender(t.ali));
+    assert_eq!(
+        t.allowance_record(t.ali, t.bob),
+        Some(Allowance {
+            amount,
+            expires_at: Some(u64::MAX),
+        })
+    );
+
+    // Spending the allowance keeps its expiry.
+    t.transfer_from(t.ali, t.joe, transfer_amount, Sender(t.bob));
+    assert_eq!(
+        t.allowance_record(t.ali, t.bob),
+        Some(Allowance {
+            amount: amount - transfer_amount,
+            expires_at: Some(u64::MAX),
+        })
+    );
+
+    t.increase_allowance(t.bob, transfer_amount, Sender(t.ali));
+    assert_eq!(
+        t.allowance_record(t.ali, t.bob),
+        Some(Allowance {
+            amount,
+            expires_at: Some(u64::MAX),
+        })
+    );
+
+    // Plain approval never expires.
+    t.approve(t.bob, amount, Sender(t.ali));
+    assert_eq!(
+        t.allowance_record(t.ali, t.bob),
+        Some(Allowance {
+            amount,
+            expires_at: None,
+        })
+    );
+}
+
+#[should_panic(expected = "ApiError::User(2) [65538]")]
+#[test]
+fn should_not_transfer_from_with_expired_allowance() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+    t.approve_with_expiry(t.bob, amount, 0, Sender(t.ali));
+    t.transfer_from(t.ali, t.joe, amount, Sender(t.bob));
+}
+
+#[test]
+fn should_not_decrement_unlimited_allowance() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+
+    t.approve(t.bob, TokenAmount::max_value(), Sender(t.ali));
+    t.transfer_from(t.ali, t.joe, amount, Sender(t.bob));
+    t.transfer_from(t.ali, t.joe, amount, Sender(t.bob));
+
+    assert_eq!(t.allowance(t.ali, t.bob), Some(TokenAmount::max_value()));
+    assert_eq!(t.balance_of(t.joe), Some(amount * 2));
+}
+
+#[test]
+fn should_decrement_allowance_below_unlimited() {
+    let amount = TokenAmount::from(100);
+    let mut t = Token::deploy();
+
+    t.approve(t.bob, TokenAmount::max_value() - 1, Sender(t.ali));
+    t.transfer_from(t.ali, t.joe, amount, Sender(t.bob));
+
+    assert_eq!(
+        t.allowance(t.ali, t.bob),
+        Some(TokenAmount::max_value() - 1 - amount)
+    );
+    assert_eq!(t.balance_of(t.joe), Some(amount));
+}
@@ -0,0 +1,40 @@
+#![no_std]
+#![no_main]
+
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractHash, RuntimeArgs};
+
+use erc20::{
+    amount::{self, TokenAmount},
+    constants::{ARG_AMOUNT, ARG_PURSE, METHOD_DEPOSIT},
+};
+
+const ARG_TOKEN_CONTRACT: &str = "token_contract";
+
+/// Deposits CSPR into a wrapped token.
+///
+/// The token is given a new purse funded with exactly the deposited amount, so it never gets access to the main purse of the account.
+#[no_mangle]
+pub extern "C" fn call() {
+    let token_contract: ContractHash = runtime::get_named_arg(ARG_TOKEN_CONTRACT);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(
+        account::get_main_purse(),
+        purse,
+        amount::to_motes(amount),
+        None,
+    )
+    .unwrap_or_revert();
+
+    let deposit_args = runtime_args! {
+        ARG_PURSE => purse,
+        ARG_AMOUNT => amount,
+    };
+
+    runtime::call_contract::<()>(token_contract, METHOD_DEPOSIT, deposit_args);
+}
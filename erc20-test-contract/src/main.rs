@@ -0,0 +1,366 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec, vec::Vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, system::CallStackElement, ApiError, CLType, CLTyped, ContractHash, EntryPoint,
+    EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs,
+};
+
+use erc20::{
+    address::Address,
+    amount::TokenAmount,
+    constants::{
+        ARG_ADDRESS, ARG_AMOUNT, ARG_DATA, ARG_FEE, ARG_FROM, ARG_INITIATOR, ARG_OPERATOR,
+        ARG_OWNER, ARG_RECIPIENT, ARG_SNAPSHOT_ID, ARG_SPENDER, ARG_TIMESTAMP, METHOD_APPROVE,
+        METHOD_BALANCE_OF_AT, METHOD_GET_PAST_VOTES, METHOD_ON_APPROVAL_RECEIVED,
+        METHOD_ON_FLASH_LOAN, METHOD_ON_TRANSFER_RECEIVED, METHOD_TOTAL_SUPPLY_AT, METHOD_TRANSFER,
+        METHOD_TRANSFER_FROM,
+    },
+    detail::ret,
+    host::{CasperHost, Host},
+};
+
+const TEST_CONTRACT_KEY: &str = "erc20_test_contract";
+const TEST_CONTRACT_PACKAGE_KEY: &str = "erc20_test_contract_package";
+const PAST_VOTES_KEY: &str = "past_votes";
+const BALANCE_AT_KEY: &str = "balance_at";
+const TOTAL_SUPPLY_AT_KEY: &str = "total_supply_at";
+const RECEIVED_AMOUNT_KEY: &str = "received_amount";
+
+const ARG_TOKEN_CONTRACT: &str = "token_contract";
+
+const METHOD_TRANSFER_AS_STORED_CONTRACT: &str = "transfer_as_stored_contract";
+const METHOD_APPROVE_AS_STORED_CONTRACT: &str = "approve_as_stored_contract";
+const METHOD_TRANSFER_FROM_AS_STORED_CONTRACT: &str = "transfer_from_as_stored_contract";
+const METHOD_STORE_PAST_VOTES: &str = "store_past_votes";
+const METHOD_STORE_BALANCE_OF_AT: &str = "store_balance_of_at";
+const METHOD_STORE_TOTAL_SUPPLY_AT: &str = "store_total_supply_at";
+
+/// Flash loan is accepted and repaid.
+const FLASH_LOAN_REPAY: u8 = 0;
+/// Flash loan is rejected by the receiver.
+const FLASH_LOAN_REJECT: u8 = 1;
+/// Flash loan is accepted, but borrowed tokens are sent to the initiator instead of being repaid.
+const FLASH_LOAN_KEEP: u8 = 2;
+
+/// Tokens transferred or approved to the contract are accepted.
+const RECEIVER_ACCEPT: u8 = 0;
+/// Tokens transferred or approved to the contract are rejected.
+const RECEIVER_REJECT: u8 = 1;
+/// Tokens approved to the contract are accepted and transferred to the contract right away.
+const RECEIVER_PULL: u8 = 2;
+
+/// Gets the hash of the contract that called the current entry point.
+fn get_calling_contract_hash() -> ContractHash {
+    let call_stack = runtime::get_call_stack();
+    match call_stack.into_iter().rev().nth(1) {
+        Some(CallStackElement::StoredContract { contract_hash, .. }) => contract_hash,
+        _ => runtime::revert(ApiError::InvalidCallerInfoRequest),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_as_stored_contract() {
+    let token_contract: ContractHash = runtime::get_named_arg(ARG_TOKEN_CONTRACT);
+    let recipient: Address = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+
+    let transfer_args = runtime_args! {
+        ARG_RECIPIENT => recipient,
+        ARG_AMOUNT => amount,
+    };
+
+    runtime::call_contract::<()>(token_contract, METHOD_TRANSFER, transfer_args);
+}
+
+#[no_mangle]
+pub extern "C" fn approve_as_stored_contract() {
+    let token_contract: ContractHash = runtime::get_named_arg(ARG_TOKEN_CONTRACT);
+    let spender: Address = runtime::get_named_arg(ARG_SPENDER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+
+    let approve_args = runtime_args! {
+        ARG_SPENDER => spender,
+        ARG_AMOUNT => amount,
+    };
+
+    runtime::call_contract::<()>(token_contract, METHOD_APPROVE, approve_args);
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_from_as_stored_contract() {
+    let token_contract: ContractHash = runtime::get_named_arg(ARG_TOKEN_CONTRACT);
+    let owner: Address = runtime::get_named_arg(ARG_OWNER);
+    let recipient: Address = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+
+    let transfer_from_args = runtime_args! {
+        ARG_OWNER => owner,
+        ARG_RECIPIENT => recipient,
+        ARG_AMOUNT => amount,
+    };
+
+    runtime::call_contract::<()>(token_contract, METHOD_TRANSFER_FROM, transfer_from_args);
+}
+
+/// Stores the result of `get_past_votes` of the token, so it can be queried by tests.
+#[no_mangle]
+pub extern "C" fn store_past_votes() {
+    let token_contract: ContractHash = runtime::get_named_arg(ARG_TOKEN_CONTRACT);
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let timestamp: u64 = runtime::get_named_arg(ARG_TIMESTAMP);
+
+    let get_past_votes_args = runtime_args! {
+        ARG_ADDRESS => address,
+        ARG_TIMESTAMP => timestamp,
+    };
+
+    let past_votes: TokenAmount =
+        runtime::call_contract(token_contract, METHOD_GET_PAST_VOTES, get_past_votes_args);
+    runtime::put_key(PAST_VOTES_KEY, storage::new_uref(past_votes).into());
+}
+
+/// Stores the result of `balance_of_at` of the token, so it can be queried by tests.
+#[no_mangle]
+pub extern "C" fn store_balance_of_at() {
+    let token_contract: ContractHash = runtime::get_named_arg(ARG_TOKEN_CONTRACT);
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let snapshot_id: u64 = runtime::get_named_arg(ARG_SNAPSHOT_ID);
+
+    let balance_of_at_args = runtime_args! {
+        ARG_ADDRESS => address,
+        ARG_SNAPSHOT_ID => snapshot_id,
+    };
+
+    let balance: TokenAmount =
+        runtime::call_contract(token_contract, METHOD_BALANCE_OF_AT, balance_of_at_args);
+    runtime::put_key(BALANCE_AT_KEY, storage::new_uref(balance).into());
+}
+
+/// Stores the result of `total_supply_at` of the token, so it can be queried by tests.
+#[no_mangle]
+pub extern "C" fn store_total_supply_at() {
+    let token_contract: ContractHash = runtime::get_named_arg(ARG_TOKEN_CONTRACT);
+    let snapshot_id: u64 = runtime::get_named_arg(ARG_SNAPSHOT_ID);
+
+    let total_supply_at_args = runtime_args! {
+        ARG_SNAPSHOT_ID => snapshot_id,
+    };
+
+    let total_supply: TokenAmount =
+        runtime::call_contract(token_contract, METHOD_TOTAL_SUPPLY_AT, total_supply_at_args);
+    runtime::put_key(TOTAL_SUPPLY_AT_KEY, storage::new_uref(total_supply).into());
+}
+
+#[no_mangle]
+pub extern "C" fn on_flash_loan() {
+    let initiator: Address = runtime::get_named_arg(ARG_INITIATOR);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let _fee: TokenAmount = runtime::get_named_arg(ARG_FEE);
+    let data: Vec<u8> = runtime::get_named_arg(ARG_DATA);
+
+    let behavior = data.first().copied().unwrap_or(FLASH_LOAN_REPAY);
+    match behavior {
+        FLASH_LOAN_REPAY => ret(true),
+        FLASH_LOAN_REJECT => ret(false),
+        FLASH_LOAN_KEEP => {
+            let transfer_args = runtime_args! {
+                ARG_RECIPIENT => initiator,
+                ARG_AMOUNT => amount,
+            };
+            runtime::call_contract::<()>(
+                get_calling_contract_hash(),
+                METHOD_TRANSFER,
+                transfer_args,
+            );
+            ret(true)
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn on_transfer_received() {
+    let _operator: Address = runtime::get_named_arg(ARG_OPERATOR);
+    let _from: Address = runtime::get_named_arg(ARG_FROM);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let data: Vec<u8> = runtime::get_named_arg(ARG_DATA);
+
+    // Amount reported by the token is stored, so tests can compare it with the balance received.
+    runtime::put_key(RECEIVED_AMOUNT_KEY, storage::new_uref(amount).into());
+
+    let behavior = data.first().copied().unwrap_or(RECEIVER_ACCEPT);
+    ret(behavior != RECEIVER_REJECT)
+}
+
+#[no_mangle]
+pub extern "C" fn on_approval_received() {
+    let owner: Address = runtime::get_named_arg(ARG_OWNER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let data: Vec<u8> = runtime::get_named_arg(ARG_DATA);
+
+    let behavior = data.first().copied().unwrap_or(RECEIVER_ACCEPT);
+    match behavior {
+        RECEIVER_ACCEPT => ret(true),
+        RECEIVER_REJECT => ret(false),
+        RECEIVER_PULL => {
+            let contract_package_hash = CasperHost
+                .get_current_contract_package_hash()
+                .unwrap_or_revert();
+            let transfer_from_args = runtime_args! {
+                ARG_OWNER => owner,
+                ARG_RECIPIENT => Address::from(contract_package_hash),
+                ARG_AMOUNT => amount,
+            };
+            runtime::call_contract::<()>(
+                get_calling_contract_hash(),
+                METHOD_TRANSFER_FROM,
+                transfer_from_args,
+            );
+            ret(true)
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+
+    let transfer_as_stored_contract_entrypoint = EntryPoint::new(
+        String::from(METHOD_TRANSFER_AS_STORED_CONTRACT),
+        vec![
+            Parameter::new(ARG_TOKEN_CONTRACT, ContractHash::cl_type()),
+            Parameter::new(ARG_RECIPIENT, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let approve_as_stored_contract_entrypoint = EntryPoint::new(
+        String::from(METHOD_APPROVE_AS_STORED_CONTRACT),
+        vec![
+            Parameter::new(ARG_TOKEN_CONTRACT, ContractHash::cl_type()),
+            Parameter::new(ARG_SPENDER, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let transfer_from_as_stored_contract_entrypoint = EntryPoint::new(
+        String::from(METHOD_TRANSFER_FROM_AS_STORED_CONTRACT),
+        vec![
+            Parameter::new(ARG_TOKEN_CONTRACT, ContractHash::cl_type()),
+            Parameter::new(ARG_OWNER, Address::cl_type()),
+            Parameter::new(ARG_RECIPIENT, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let store_past_votes_entrypoint = EntryPoint::new(
+        String::from(METHOD_STORE_PAST_VOTES),
+        vec![
+            Parameter::new(ARG_TOKEN_CONTRACT, ContractHash::cl_type()),
+            Parameter::new(ARG_ADDRESS, Address::cl_type()),
+            Parameter::new(ARG_TIMESTAMP, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let store_balance_of_at_entrypoint = EntryPoint::new(
+        String::from(METHOD_STORE_BALANCE_OF_AT),
+        vec![
+            Parameter::new(ARG_TOKEN_CONTRACT, ContractHash::cl_type()),
+            Parameter::new(ARG_ADDRESS, Address::cl_type()),
+            Parameter::new(ARG_SNAPSHOT_ID, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let store_total_supply_at_entrypoint = EntryPoint::new(
+        String::from(METHOD_STORE_TOTAL_SUPPLY_AT),
+        vec![
+            Parameter::new(ARG_TOKEN_CONTRACT, ContractHash::cl_type()),
+            Parameter::new(ARG_SNAPSHOT_ID, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let on_flash_loan_entrypoint = EntryPoint::new(
+        String::from(METHOD_ON_FLASH_LOAN),
+        vec![
+            Parameter::new(ARG_INITIATOR, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            Parameter::new(ARG_FEE, TokenAmount::cl_type()),
+            Parameter::new(ARG_DATA, Vec::<u8>::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let on_transfer_received_entrypoint = EntryPoint::new(
+        String::from(METHOD_ON_TRANSFER_RECEIVED),
+        vec![
+            Parameter::new(ARG_OPERATOR, Address::cl_type()),
+            Parameter::new(ARG_FROM, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            Parameter::new(ARG_DATA, Vec::<u8>::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let on_approval_received_entrypoint = EntryPoint::new(
+        String::from(METHOD_ON_APPROVAL_RECEIVED),
+        vec![
+            Parameter::new(ARG_OWNER, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            Parameter::new(ARG_DATA, Vec::<u8>::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    entry_points.add_entry_point(transfer_as_stored_contract_entrypoint);
+    entry_points.add_entry_point(approve_as_stored_contract_entrypoint);
+    entry_points.add_entry_point(transfer_from_as_stored_contract_entrypoint);
+    entry_points.add_entry_point(store_past_votes_entrypoint);
+    entry_points.add_entry_point(store_balance_of_at_entrypoint);
+    entry_points.add_entry_point(store_total_supply_at_entrypoint);
+    entry_points.add_entry_point(on_flash_loan_entrypoint);
+    entry_points.add_entry_point(on_transfer_received_entrypoint);
+    entry_points.add_entry_point(on_approval_received_entrypoint);
+
+    let (contract_hash, _version) = storage::new_contract(
+        entry_points,
+        None,
+        Some(TEST_CONTRACT_PACKAGE_KEY.into()),
+        None,
+    );
+
+    runtime::put_key(TEST_CONTRACT_KEY, Key::from(contract_hash));
+}
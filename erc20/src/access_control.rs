@@ -0,0 +1,140 @@
+//! Implementation of a role-based access control.
+//!
+//! Each role has an admin role, and only addresses that have the admin role of a given role are allowed to grant and revoke it. Admin role of every role is [`ADMIN_ROLE`] unless it is changed with [`set_role_admin`].
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_types::{bytesrepr::ToBytes, URef};
+
+use crate::{
+    address::Address,
+    constants::{ROLES_KEY, ROLE_ADMINS_KEY},
+    error::Error,
+    host::{Host, UnwrapOrRevert as _},
+};
+
+/// Role that administers all other roles by default.
+pub const ADMIN_ROLE: &str = "admin";
+/// Role that is allowed to mint and burn tokens.
+pub const MINTER_ROLE: &str = "minter";
+/// Role that is allowed to pause and unpause the token.
+pub const PAUSER_ROLE: &str = "pauser";
+
+#[inline]
+fn get_roles_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(ROLES_KEY)
+}
+
+#[inline]
+fn get_role_admins_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(ROLE_ADMINS_KEY)
+}
+
+/// Creates a dictionary item key for a (role, address) pair.
+fn make_role_item_key<H: Host>(host: &H, role: &str, address: &Address) -> String {
+    let mut preimage = Vec::new();
+    preimage.append(&mut role.to_bytes().unwrap_or_revert_on(host));
+    preimage.append(&mut address.to_bytes().unwrap_or_revert_on(host));
+
+    let key_bytes = host.blake2b(&preimage);
+    hex::encode(&key_bytes)
+}
+
+/// Creates a dictionary item key for a role.
+fn make_role_admin_item_key<H: Host>(host: &H, role: &str) -> String {
+    let preimage = role.to_bytes().unwrap_or_revert_on(host);
+    let key_bytes = host.blake2b(&preimage);
+    hex::encode(&key_bytes)
+}
+
+/// Writes a role membership of a specified address into a dictionary.
+pub fn write_role_into<H: Host>(
+    host: &H,
+    roles_uref: URef,
+    role: &str,
+    address: &Address,
+    has_role: bool,
+) {
+    let dictionary_item_key = make_role_item_key(host, role, address);
+    host.dictionary_put(roles_uref, &dictionary_item_key, has_role);
+}
+
+/// Checks if a specified address has a role.
+pub fn has_role<H: Host>(host: &H, role: &str, address: &Address) -> bool {
+    let roles_uref = get_roles_uref(host);
+    let dictionary_item_key = make_role_item_key(host, role, address);
+
+    host.dictionary_get(roles_uref, &dictionary_item_key)
+        .unwrap_or_default()
+}
+
+/// Returns the admin role of a role.
+pub fn get_role_admin<H: Host>(host: &H, role: &str) -> String {
+    let role_admins_uref = get_role_admins_uref(host);
+    let dictionary_item_key = make_role_admin_item_key(host, role);
+
+    host.dictionary_get(role_admins_uref, &dictionary_item_key)
+        .unwrap_or_else(|| ADMIN_ROLE.to_string())
+}
+
+/// Sets the admin role of a role.
+///
+/// # Security
+///
+/// This offers no security whatsoever, and for all practical purposes user of this function is advised to NOT expose this function through a public entry point.
+pub fn set_role_admin<H: Host>(host: &H, role: &str, admin_role: &str) {
+    let role_admins_uref = get_role_admins_uref(host);
+    let dictionary_item_key = make_role_admin_item_key(host, role);
+    host.dictionary_put(
+        role_admins_uref,
+        &dictionary_item_key,
+        admin_role.to_string(),
+    );
+}
+
+/// This function makes sure that the immediate caller has a role.
+///
+/// An attempt to call this function by an address without the role will fail with [`Error::Unauthorized`].
+pub fn require_role<H: Host>(host: &H, role: &str) -> Result<(), Error> {
+    let caller = host.get_immediate_caller()?;
+
+    if has_role(host, role, &caller) {
+        Ok(())
+    } else {
+        Err(Error::Unauthorized)
+    }
+}
+
+/// Grants a role to an address.
+///
+/// Only an address with the admin role of the `role` is allowed to grant it.
+pub fn grant_role<H: Host>(host: &H, role: &str, address: &Address) -> Result<(), Error> {
+    require_role(host, &get_role_admin(host, role))?;
+    write_role_into(host, get_roles_uref(host), role, address, true);
+    Ok(())
+}
+
+/// Revokes a role from an address.
+///
+/// Only an address with the admin role of the `role` is allowed to revoke it.
+pub fn revoke_role<H: Host>(host: &H, role: &str, address: &Address) -> Result<(), Error> {
+    require_role(host, &get_role_admin(host, role))?;
+    write_role_into(host, get_roles_uref(host), role, address, false);
+    Ok(())
+}
+
+/// Renounces a role of the caller.
+///
+/// The `address` has to be the immediate caller, which prevents renouncing a role by mistake.
+pub fn renounce_role<H: Host>(host: &H, role: &str, address: &Address) -> Result<(), Error> {
+    let caller = host.get_immediate_caller()?;
+
+    if caller != *address {
+        return Err(Error::Unauthorized);
+    }
+
+    write_role_into(host, get_roles_uref(host), role, address, false);
+    Ok(())
+}
@@ -1,43 +1,304 @@
 //! Implementation of allowances.
-use alloc::string::String;
+//!
+//! An allowance may have an expiry block time, after which it is treated as if it was never granted. An allowance equal to [`UNLIMITED_ALLOWANCE`] is unlimited, and spending it doesn't decrement it.
+use alloc::{string::String, vec::Vec};
 
-use casper_contract::{
-    contract_api::{runtime, storage},
-    unwrap_or_revert::UnwrapOrRevert,
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, URef,
 };
-use casper_types::{account::AccountHash, URef, U512};
 
-use crate::{constants::ALLOWANCES_KEY, detail};
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    constants::ALLOWANCES_KEY,
+    host::{Host, UnwrapOrRevert as _},
+};
+
+/// Allowance that lets a spender transfer any amount of tokens.
+pub const UNLIMITED_ALLOWANCE: TokenAmount = TokenAmount::max_value();
+
+/// Allowance of a spender over the tokens of an owner.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Allowance {
+    /// Amount the spender is allowed to spend.
+    pub amount: TokenAmount,
+    /// Block time at which the allowance expires, or `None` if it never expires.
+    pub expires_at: Option<u64>,
+}
+
+impl Allowance {
+    /// Checks if the allowance is expired at a given block time.
+    pub fn is_expired(&self, blocktime: u64) -> bool {
+        matches!(self.expires_at, Some(expires_at) if blocktime >= expires_at)
+    }
+}
 
-static mut ALLOWANCES_UREF: Option<URef> = None;
+impl CLTyped for Allowance {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Allowance {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(self.serialized_length());
+        result.append(&mut self.amount.to_bytes()?);
+        result.append(&mut self.expires_at.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.amount.serialized_length() + self.expires_at.serialized_length()
+    }
+}
+
+impl FromBytes for Allowance {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (amount, remainder) = TokenAmount::from_bytes(bytes)?;
+        let (expires_at, remainder) = Option::<u64>::from_bytes(remainder)?;
+        Ok((Allowance { amount, expires_at }, remainder))
+    }
+}
 
 #[inline]
-fn get_allowances_uref() -> URef {
-    // TODO: unsafe impl Sync for URef {}
-    unsafe { *ALLOWANCES_UREF.get_or_insert_with(|| detail::get_uref(ALLOWANCES_KEY)) }
+fn get_allowances_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(ALLOWANCES_KEY)
 }
 
 /// Creates a dictionary item key for a (owner, spender) pair.
-fn make_dictionary_item_key(owner: &AccountHash, spender: &AccountHash) -> String {
-    let mut preimage = [0; 64];
-    preimage[..32].copy_from_slice(owner.as_bytes());
-    preimage[32..].copy_from_slice(spender.as_bytes());
-    let key_bytes = runtime::blake2b(&preimage);
+fn make_dictionary_item_key<H: Host>(host: &H, owner: &Address, spender: &Address) -> String {
+    let mut preimage = Vec::new();
+    preimage.append(&mut owner.to_bytes().unwrap_or_revert_on(host));
+    preimage.append(&mut spender.to_bytes().unwrap_or_revert_on(host));
+
+    let key_bytes = host.blake2b(&preimage);
     hex::encode(&key_bytes)
 }
 
-/// Writes an allowance for owner and spender for a specific amount.
-pub fn write_allowance(owner: &AccountHash, spender: &AccountHash, amount: U512) {
-    let allowance_uref = get_allowances_uref();
-    let dictionary_item_key = make_dictionary_item_key(owner, spender);
-    storage::dictionary_put(allowance_uref, &dictionary_item_key, amount)
+/// Writes an allowance record for owner and spender.
+pub fn write_allowance_record<H: Host>(
+    host: &H,
+    owner: &Address,
+    spender: &Address,
+    allowance: Allowance,
+) {
+    let allowance_uref = get_allowances_uref(host);
+    let dictionary_item_key = make_dictionary_item_key(host, owner, spender);
+    host.dictionary_put(allowance_uref, &dictionary_item_key, allowance)
+}
+
+/// Reads an allowance record for owner and spender.
+///
+/// An expired allowance is returned with a zero amount, but keeps its expiry so that updating it doesn't make it valid again.
+pub fn read_allowance_record<H: Host>(host: &H, owner: &Address, spender: &Address) -> Allowance {
+    let allowance_uref = get_allowances_uref(host);
+    let dictionary_item_key = make_dictionary_item_key(host, owner, spender);
+    let allowance: Allowance = host
+        .dictionary_get(allowance_uref, &dictionary_item_key)
+        .unwrap_or_default();
+
+    if allowance.is_expired(host.get_blocktime()) {
+        Allowance {
+            amount: TokenAmount::zero(),
+            ..allowance
+        }
+    } else {
+        allowance
+    }
+}
+
+/// Writes an allowance for owner and spender for a specific amount that never expires.
+pub fn write_allowance<H: Host>(host: &H, owner: &Address, spender: &Address, amount: TokenAmount) {
+    write_allowance_record(
+        host,
+        owner,
+        spender,
+        Allowance {
+            amount,
+            expires_at: None,
+        },
+    )
+}
+
+/// Changes the amount of an allowance for owner and spender, keeping its expiry.
+///
+/// An expired allowance stays expired, so its new amount can't be spent.
+pub fn update_allowance<H: Host>(
+    host: &H,
+    owner: &Address,
+    spender: &Address,
+    amount: TokenAmount,
+) {
+    let allowance = read_allowance_record(host, owner, spender);
+    write_allowance_record(
+        host,
+        owner,
+        spender,
+        Allowance {
+            amount,
+            ..allowance
+        },
+    )
 }
 
 /// Reads an allowance for a owner and spender
-pub fn read_allowance(owner: &AccountHash, spender: &AccountHash) -> U512 {
-    let allowance_uref = get_allowances_uref();
-    let dictionary_item_key = make_dictionary_item_key(owner, spender);
-    storage::dictionary_get(allowance_uref, &dictionary_item_key)
-        .unwrap_or_revert()
-        .unwrap_or_default()
+///
+/// An expired allowance is read as zero.
+pub fn read_allowance<H: Host>(host: &H, owner: &Address, spender: &Address) -> TokenAmount {
+    read_allowance_record(host, owner, spender).amount
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::account::AccountHash;
+
+    use super::*;
+    use crate::{balances, error::Error, internal, mock::MockHost};
+
+    const OWNER: Address = Address::Account(AccountHash::new([1; 32]));
+    const SPENDER: Address = Address::Account(AccountHash::new([2; 32]));
+    const RECIPIENT: Address = Address::Account(AccountHash::new([3; 32]));
+
+    fn setup() -> MockHost {
+        let host = MockHost::new();
+        internal::mint(&host, &OWNER, TokenAmount::from(1000)).unwrap();
+        host.set_caller(SPENDER);
+        host
+    }
+
+    #[test]
+    fn should_read_expired_allowance_as_zero() {
+        let host = setup();
+
+        write_allowance_record(
+            &host,
+            &OWNER,
+            &SPENDER,
+            Allowance {
+                amount: TokenAmount::from(100),
+                expires_at: Some(10),
+            },
+        );
+
+        host.set_blocktime(9);
+        assert_eq!(
+            read_allowance(&host, &OWNER, &SPENDER),
+            TokenAmount::from(100)
+        );
+
+        host.set_blocktime(10);
+        assert_eq!(read_allowance(&host, &OWNER, &SPENDER), TokenAmount::zero());
+    }
+
+    #[test]
+    fn should_keep_expiry_when_updating_allowance() {
+        let host = setup();
+
+        write_allowance_record(
+            &host,
+            &OWNER,
+            &SPENDER,
+            Allowance {
+                amount: TokenAmount::from(100),
+                expires_at: Some(10),
+            },
+        );
+        update_allowance(&host, &OWNER, &SPENDER, TokenAmount::from(50));
+
+        assert_eq!(
+            read_allowance_record(&host, &OWNER, &SPENDER),
+            Allowance {
+                amount: TokenAmount::from(50),
+                expires_at: Some(10),
+            }
+        );
+    }
+
+    #[test]
+    fn should_not_revive_expired_allowance_on_increase() {
+        let host = setup();
+
+        write_allowance_record(
+            &host,
+            &OWNER,
+            &SPENDER,
+            Allowance {
+                amount: TokenAmount::from(100),
+                expires_at: Some(10),
+            },
+        );
+        host.set_blocktime(10);
+
+        host.set_caller(OWNER);
+        crate::increase_allowance(&host, SPENDER, TokenAmount::from(50)).unwrap();
+
+        assert_eq!(
+            read_allowance_record(&host, &OWNER, &SPENDER),
+            Allowance {
+                amount: TokenAmount::zero(),
+                expires_at: Some(10),
+            }
+        );
+
+        host.set_caller(SPENDER);
+        assert_eq!(
+            crate::transfer_from(&host, OWNER, RECIPIENT, TokenAmount::one()),
+            Err(Error::InsufficientAllowance)
+        );
+    }
+
+    #[test]
+    fn should_decrement_allowance_on_transfer_from() {
+        let host = setup();
+
+        write_allowance(&host, &OWNER, &SPENDER, TokenAmount::from(100));
+
+        crate::transfer_from(&host, OWNER, RECIPIENT, TokenAmount::from(60)).unwrap();
+
+        assert_eq!(
+            read_allowance(&host, &OWNER, &SPENDER),
+            TokenAmount::from(40)
+        );
+        assert_eq!(
+            balances::read_balance(&host, &RECIPIENT),
+            TokenAmount::from(60)
+        );
+        assert_eq!(
+            crate::transfer_from(&host, OWNER, RECIPIENT, TokenAmount::from(41)),
+            Err(Error::InsufficientAllowance)
+        );
+    }
+
+    #[test]
+    fn should_not_decrement_unlimited_allowance_on_transfer_from() {
+        let host = setup();
+
+        write_allowance(&host, &OWNER, &SPENDER, UNLIMITED_ALLOWANCE);
+
+        crate::transfer_from(&host, OWNER, RECIPIENT, TokenAmount::from(60)).unwrap();
+
+        assert_eq!(read_allowance(&host, &OWNER, &SPENDER), UNLIMITED_ALLOWANCE);
+    }
+
+    #[test]
+    fn should_not_transfer_from_with_expired_allowance() {
+        let host = setup();
+
+        write_allowance_record(
+            &host,
+            &OWNER,
+            &SPENDER,
+            Allowance {
+                amount: TokenAmount::from(100),
+                expires_at: Some(10),
+            },
+        );
+        host.set_blocktime(10);
+
+        assert_eq!(
+            crate::transfer_from(&host, OWNER, RECIPIENT, TokenAmount::one()),
+            Err(Error::InsufficientAllowance)
+        );
+    }
 }
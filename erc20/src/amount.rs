@@ -0,0 +1,51 @@
+//! Implementation of a `TokenAmount` which is the type of every token amount.
+//!
+//! Amounts are [`U512`] by default, and [`U256`] when the crate is built with the `u256` feature, which halves the storage of every amount and matches tokens bridged from Ethereum. Balances, allowances, entry point arguments and their `CLType`s all follow the choice.
+use core::mem;
+
+#[cfg(feature = "u256")]
+use casper_types::U256;
+use casper_types::U512;
+
+/// Type of a token amount.
+#[cfg(feature = "u256")]
+pub type TokenAmount = U256;
+
+/// Type of a token amount.
+#[cfg(not(feature = "u256"))]
+pub type TokenAmount = U512;
+
+/// Converts a token `amount` into an amount of motes.
+///
+/// Every token amount fits into [`U512`], so this conversion is lossless.
+pub fn to_motes(amount: TokenAmount) -> U512 {
+    let mut bytes = [0u8; mem::size_of::<U512>()];
+    amount.to_little_endian(&mut bytes[..mem::size_of::<TokenAmount>()]);
+    U512::from_little_endian(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_amount_to_motes() {
+        assert_eq!(to_motes(TokenAmount::zero()), U512::zero());
+        assert_eq!(
+            to_motes(TokenAmount::from(1_000_000_000u64)),
+            U512::from(1_000_000_000u64)
+        );
+    }
+
+    #[cfg(not(feature = "u256"))]
+    #[test]
+    fn should_convert_largest_amount_to_motes_without_overflow() {
+        assert_eq!(to_motes(TokenAmount::max_value()), U512::max_value());
+    }
+
+    #[cfg(feature = "u256")]
+    #[test]
+    fn should_convert_largest_amount_to_motes_without_overflow() {
+        assert_eq!(to_motes(TokenAmount::max_value()), (U512::one() << 256) - 1);
+    }
+}
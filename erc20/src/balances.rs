@@ -1,72 +1,423 @@
 //! Implementation of balances.
-use alloc::string::String;
+use alloc::vec::Vec;
 
-use casper_contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{account::AccountHash, URef, U512};
+use casper_types::URef;
 
-use crate::{constants::BALANCES_KEY, detail, error::Error};
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    constants::BALANCES_KEY,
+    detail,
+    error::Error,
+    events::{self, Event},
+    fee, frozen,
+    host::Host,
+    pausable, snapshots, votes,
+};
 
-/// Creates a dictionary item key for a dictionary item.
-#[inline]
-fn make_dictionary_item_key(owner: &AccountHash) -> String {
-    hex::encode(owner.as_bytes())
+fn get_balances_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(BALANCES_KEY)
 }
 
-static mut BALANCES_UREF: Option<URef> = None;
-
-fn get_balances_uref() -> URef {
-    // TODO: unsafe impl Sync for URef {}
-    unsafe { *BALANCES_UREF.get_or_insert_with(|| detail::get_uref(BALANCES_KEY)) }
-}
+/// Writes token balance of a specified address.
+///
+/// Previous balance is recorded for the current snapshot, if it wasn't recorded already.
+pub fn write_balance<H: Host>(host: &H, address: &Address, amount: TokenAmount) {
+    snapshots::record_balance(host, address);
 
-/// Writes token balance of a specified account.
-pub fn write_balance(account_hash: &AccountHash, amount: U512) {
-    let balances_uref = get_balances_uref();
-    write_balance_into(balances_uref, account_hash, amount);
+    let balances_uref = get_balances_uref(host);
+    write_balance_into(host, balances_uref, address, amount);
 }
 
-/// Writes token balance of a specified account into a dictionary.
-pub fn write_balance_into(balances_uref: URef, account_hash: &AccountHash, amount: U512) {
-    let dictionary_item_key = make_dictionary_item_key(account_hash);
-    storage::dictionary_put(balances_uref, &dictionary_item_key, amount);
+/// Writes token balance of a specified address into a dictionary.
+pub fn write_balance_into<H: Host>(
+    host: &H,
+    balances_uref: URef,
+    address: &Address,
+    amount: TokenAmount,
+) {
+    let dictionary_item_key = detail::make_dictionary_item_key(host, address);
+    host.dictionary_put(balances_uref, &dictionary_item_key, amount);
 }
 
-/// Reads token balance of a specified account.
+/// Reads token balance of a specified address.
 ///
-/// If a given account does not have balances in the system, then a 0 is returned.
-pub fn read_balance(account_hash: &AccountHash) -> U512 {
-    let balances_uref = get_balances_uref();
-    let dictionary_item_key = make_dictionary_item_key(account_hash);
+/// If a given address does not have balances in the system, then a 0 is returned.
+pub fn read_balance<H: Host>(host: &H, address: &Address) -> TokenAmount {
+    let balances_uref = get_balances_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, address);
 
-    storage::dictionary_get(balances_uref, &dictionary_item_key)
-        .unwrap_or_revert()
+    host.dictionary_get(balances_uref, &dictionary_item_key)
         .unwrap_or_default()
 }
 
-/// Transfer tokens from the `sender` to the `recipient`.
+/// Transfer tokens from the `sender` to the `recipient`, and emits [`Event::Transfer`].
+///
+/// If a transfer fee applies, the fee is deducted from the `amount` received by the `recipient` and moved to the fee recipient with a separate [`Event::Transfer`]. Returns the amount received by the `recipient`.
+///
+/// This function should not be used directly by contract's entrypoint as it does not validate the sender. It fails with [`Error::Paused`] if the token is paused, and with [`Error::AccountFrozen`] if the `sender`, the `recipient`, or the recipient of a fee is frozen.
+pub fn transfer_balance<H: Host>(
+    host: &H,
+    sender: &Address,
+    recipient: &Address,
+    amount: TokenAmount,
+) -> Result<TokenAmount, Error> {
+    let fee = fee::calculate_fee(host, sender, recipient, amount)?;
+    move_balance(host, sender, recipient, amount, fee)
+}
+
+/// Transfer tokens from the `sender` to the `recipient` without a transfer fee, and emits [`Event::Transfer`].
 ///
-/// This function should not be used directly by contract's entrypoint as it does not validate the sender.
-pub fn transfer_balance(
-    sender: &AccountHash,
-    recipient: &AccountHash,
-    amount: U512,
+/// This is meant for tokens moved by the token itself, such as tokens locked for vesting and released from it, which are not subject to the fee policy. It fails the same way as [`transfer_balance`].
+pub fn transfer_balance_without_fee<H: Host>(
+    host: &H,
+    sender: &Address,
+    recipient: &Address,
+    amount: TokenAmount,
 ) -> Result<(), Error> {
+    move_balance(host, sender, recipient, amount, None)?;
+    Ok(())
+}
+
+/// Moves tokens from the `sender` to the `recipient`, and a `fee` out of them to its recipient.
+fn move_balance<H: Host>(
+    host: &H,
+    sender: &Address,
+    recipient: &Address,
+    amount: TokenAmount,
+    fee: Option<(Address, TokenAmount)>,
+) -> Result<TokenAmount, Error> {
+    pausable::requires_not_paused(host)?;
+    frozen::requires_not_frozen(host, sender)?;
+    frozen::requires_not_frozen(host, recipient)?;
+
+    if let Some((fee_recipient, _)) = fee {
+        frozen::requires_not_frozen(host, &fee_recipient)?;
+    }
+    let received_amount = match fee {
+        // Fee never exceeds the amount, as it's at most 10 000 basis points of it.
+        Some((_, fee_amount)) => amount - fee_amount,
+        None => amount,
+    };
+
     let new_sender_balance = {
-        let sender_balance = read_balance(sender);
+        let sender_balance = read_balance(host, sender);
         sender_balance
             .checked_sub(amount)
             .ok_or(Error::InsufficientBalance)?
     };
 
     let new_recipient_balance = {
-        let recipient_balance = read_balance(recipient);
+        // A transfer to self is credited on top of the debited balance, so it doesn't create tokens.
+        let recipient_balance = if sender == recipient {
+            new_sender_balance
+        } else {
+            read_balance(host, recipient)
+        };
         recipient_balance
-            .checked_add(amount)
+            .checked_add(received_amount)
             .ok_or(Error::Overflow)?
     };
 
-    write_balance(sender, new_sender_balance);
-    write_balance(recipient, new_recipient_balance);
+    write_balance(host, sender, new_sender_balance);
+    write_balance(host, recipient, new_recipient_balance);
+
+    votes::move_voting_power(
+        host,
+        votes::read_delegate(host, sender),
+        votes::read_delegate(host, recipient),
+        received_amount,
+    )?;
+
+    events::emit(
+        host,
+        Event::Transfer {
+            from: Some(*sender),
+            to: Some(*recipient),
+            amount: received_amount,
+        },
+    );
+
+    if let Some((fee_recipient, fee_amount)) = fee {
+        // Balance is read after the transfer is written, as the fee recipient may be either side of it.
+        credit_balance(host, sender, &fee_recipient, fee_amount)?;
+    }
+
+    Ok(received_amount)
+}
+
+/// Credits tokens already debited from the `sender` to the `recipient`, and emits [`Event::Transfer`].
+fn credit_balance<H: Host>(
+    host: &H,
+    sender: &Address,
+    recipient: &Address,
+    amount: TokenAmount,
+) -> Result<(), Error> {
+    // Credited tokens are already part of the total supply, so the balance can't overflow.
+    let new_recipient_balance = read_balance(host, recipient)
+        .checked_add(amount)
+        .ok_or(Error::Overflow)?;
+
+    write_balance(host, recipient, new_recipient_balance);
+
+    votes::move_voting_power(
+        host,
+        votes::read_delegate(host, sender),
+        votes::read_delegate(host, recipient),
+        amount,
+    )?;
+
+    events::emit(
+        host,
+        Event::Transfer {
+            from: Some(*sender),
+            to: Some(*recipient),
+            amount,
+        },
+    );
 
     Ok(())
 }
+
+/// Transfer tokens from the `sender` to many recipients at once.
+///
+/// Every recipient and recipient of a fee is checked, and the total amount is checked against the balance of the `sender`, before any balance is written, so either all transfers are made or none of them is. The `sender` is debited once, and then each recipient is credited with fees, frozen accounts and events applying to each transfer as in [`transfer_balance`]. This function should not be used directly by contract's entrypoint as it does not validate the sender.
+pub fn batch_transfer_balance<H: Host>(
+    host: &H,
+    sender: &Address,
+    recipients: &[Address],
+    amounts: &[TokenAmount],
+) -> Result<(), Error> {
+    if recipients.len() != amounts.len() {
+        return Err(Error::LengthMismatch);
+    }
+
+    pausable::requires_not_paused(host)?;
+    frozen::requires_not_frozen(host, sender)?;
+
+    let mut fees = Vec::with_capacity(recipients.len());
+    let mut total_amount = TokenAmount::zero();
+    for (recipient, amount) in recipients.iter().zip(amounts) {
+        frozen::requires_not_frozen(host, recipient)?;
+
+        let fee = fee::calculate_fee(host, sender, recipient, *amount)?;
+        if let Some((fee_recipient, _)) = fee {
+            frozen::requires_not_frozen(host, &fee_recipient)?;
+        }
+        fees.push(fee);
+
+        total_amount = total_amount.checked_add(*amount).ok_or(Error::Overflow)?;
+    }
+
+    let new_sender_balance = read_balance(host, sender)
+        .checked_sub(total_amount)
+        .ok_or(Error::InsufficientBalance)?;
+    write_balance(host, sender, new_sender_balance);
+
+    for ((recipient, amount), fee) in recipients.iter().zip(amounts).zip(fees) {
+        match fee {
+            Some((fee_recipient, fee_amount)) => {
+                // Fee never exceeds the amount, as it's at most 10 000 basis points of it.
+                credit_balance(host, sender, recipient, *amount - fee_amount)?;
+                credit_balance(host, sender, &fee_recipient, fee_amount)?;
+            }
+            None => credit_balance(host, sender, recipient, *amount)?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::account::AccountHash;
+
+    use super::*;
+    use crate::{internal, mock::MockHost};
+
+    const ALICE: Address = Address::Account(AccountHash::new([1; 32]));
+    const BOB: Address = Address::Account(AccountHash::new([2; 32]));
+    const TREASURY: Address = Address::Account(AccountHash::new([3; 32]));
+
+    fn setup(initial_balance: u64) -> MockHost {
+        let host = MockHost::new();
+        internal::mint(&host, &ALICE, TokenAmount::from(initial_balance)).unwrap();
+        host
+    }
+
+    #[test]
+    fn should_transfer_balance() {
+        let host = setup(1000);
+
+        transfer_balance(&host, &ALICE, &BOB, TokenAmount::from(300)).unwrap();
+
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(700));
+        assert_eq!(read_balance(&host, &BOB), TokenAmount::from(300));
+    }
+
+    #[test]
+    fn should_not_change_balance_on_transfer_to_self() {
+        let host = setup(1000);
+
+        transfer_balance(&host, &ALICE, &ALICE, TokenAmount::from(300)).unwrap();
+
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(1000));
+        assert_eq!(
+            crate::total_supply::read_total_supply(&host),
+            TokenAmount::from(1000)
+        );
+    }
+
+    #[test]
+    fn should_not_transfer_more_than_balance() {
+        let host = setup(1000);
+
+        assert_eq!(
+            transfer_balance(&host, &ALICE, &BOB, TokenAmount::from(1001)),
+            Err(Error::InsufficientBalance)
+        );
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(1000));
+    }
+
+    #[test]
+    fn should_not_transfer_from_or_to_frozen_address() {
+        let host = setup(1000);
+
+        frozen::write_frozen(&host, &BOB, true);
+
+        assert_eq!(
+            transfer_balance(&host, &ALICE, &BOB, TokenAmount::one()),
+            Err(Error::AccountFrozen)
+        );
+        assert_eq!(
+            transfer_balance(&host, &BOB, &ALICE, TokenAmount::zero()),
+            Err(Error::AccountFrozen)
+        );
+    }
+
+    #[test]
+    fn should_not_transfer_with_frozen_fee_recipient() {
+        let host = setup(1000);
+
+        fee::write_fee_policy(&host, 250, &TREASURY).unwrap();
+        frozen::write_frozen(&host, &TREASURY, true);
+
+        assert_eq!(
+            transfer_balance(&host, &ALICE, &BOB, TokenAmount::from(400)),
+            Err(Error::AccountFrozen)
+        );
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(1000));
+    }
+
+    #[test]
+    fn should_route_transfer_fee_to_fee_recipient() {
+        let host = setup(1000);
+
+        fee::write_fee_policy(&host, 250, &TREASURY).unwrap();
+
+        transfer_balance(&host, &ALICE, &BOB, TokenAmount::from(999)).unwrap();
+
+        // 2.5% of 999 is 24.975, which is rounded down.
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(1));
+        assert_eq!(read_balance(&host, &BOB), TokenAmount::from(975));
+        assert_eq!(read_balance(&host, &TREASURY), TokenAmount::from(24));
+    }
+
+    #[test]
+    fn should_only_move_fee_on_transfer_to_self() {
+        let host = setup(1000);
+
+        fee::write_fee_policy(&host, 250, &TREASURY).unwrap();
+
+        transfer_balance(&host, &ALICE, &ALICE, TokenAmount::from(400)).unwrap();
+
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(990));
+        assert_eq!(read_balance(&host, &TREASURY), TokenAmount::from(10));
+        assert_eq!(
+            crate::total_supply::read_total_supply(&host),
+            TokenAmount::from(1000)
+        );
+    }
+
+    #[test]
+    fn should_batch_transfer_balance() {
+        let host = setup(1000);
+
+        batch_transfer_balance(
+            &host,
+            &ALICE,
+            &[BOB, TREASURY],
+            &[TokenAmount::from(100), TokenAmount::from(200)],
+        )
+        .unwrap();
+
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(700));
+        assert_eq!(read_balance(&host, &BOB), TokenAmount::from(100));
+        assert_eq!(read_balance(&host, &TREASURY), TokenAmount::from(200));
+    }
+
+    #[test]
+    fn should_not_batch_transfer_more_than_balance() {
+        let host = setup(1000);
+
+        assert_eq!(
+            batch_transfer_balance(
+                &host,
+                &ALICE,
+                &[BOB, TREASURY],
+                &[TokenAmount::from(600), TokenAmount::from(600)],
+            ),
+            Err(Error::InsufficientBalance)
+        );
+        assert_eq!(read_balance(&host, &BOB), TokenAmount::zero());
+    }
+
+    #[test]
+    fn should_batch_transfer_balance_with_fee() {
+        let host = setup(1000);
+
+        fee::write_fee_policy(&host, 250, &TREASURY).unwrap();
+
+        batch_transfer_balance(
+            &host,
+            &ALICE,
+            &[BOB, ALICE],
+            &[TokenAmount::from(400), TokenAmount::from(200)],
+        )
+        .unwrap();
+
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(595));
+        assert_eq!(read_balance(&host, &BOB), TokenAmount::from(390));
+        assert_eq!(read_balance(&host, &TREASURY), TokenAmount::from(15));
+    }
+
+    #[test]
+    fn should_not_change_balances_when_any_batch_transfer_fails() {
+        let host = setup(1000);
+
+        frozen::write_frozen(&host, &TREASURY, true);
+
+        assert_eq!(
+            batch_transfer_balance(
+                &host,
+                &ALICE,
+                &[BOB, TREASURY],
+                &[TokenAmount::from(100), TokenAmount::from(200)],
+            ),
+            Err(Error::AccountFrozen)
+        );
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(1000));
+        assert_eq!(read_balance(&host, &BOB), TokenAmount::zero());
+
+        frozen::write_frozen(&host, &TREASURY, false);
+        fee::write_fee_policy(&host, 250, &TREASURY).unwrap();
+        frozen::write_frozen(&host, &TREASURY, true);
+
+        assert_eq!(
+            batch_transfer_balance(&host, &ALICE, &[BOB], &[TokenAmount::from(100)]),
+            Err(Error::AccountFrozen)
+        );
+        assert_eq!(read_balance(&host, &ALICE), TokenAmount::from(1000));
+        assert_eq!(read_balance(&host, &BOB), TokenAmount::zero());
+    }
+}
@@ -4,88 +4,431 @@
 
 extern crate alloc;
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{account::AccountHash, system::mint::TOTAL_SUPPLY_KEY, U512};
+use casper_types::{ContractPackageHash, PublicKey, URef};
 
 use erc20::{
+    address::Address,
+    amount::TokenAmount,
     constants::{
-        ARG_ADDRESS, ARG_AMOUNT, ARG_DECIMALS, ARG_NAME, ARG_OWNER, ARG_RECIPIENT, ARG_SPENDER,
-        ARG_SYMBOL, ARG_TOTAL_SUPPLY, DECIMALS_KEY, NAME_KEY, SYMBOL_KEY,
+        ARG_ADDRESS, ARG_AMOUNT, ARG_AMOUNTS, ARG_BENEFICIARY, ARG_CAP, ARG_CLIFF, ARG_DATA,
+        ARG_DEADLINE, ARG_DECIMALS, ARG_DELEGATEE, ARG_DURATION, ARG_EXEMPT, ARG_EXPIRES_AT,
+        ARG_FEE_BASIS_POINTS, ARG_FEE_RECIPIENT, ARG_MINTABLE, ARG_NAME, ARG_NEW_OWNER, ARG_NONCE,
+        ARG_OWNER, ARG_OWNER_PUBLIC_KEY, ARG_PURSE, ARG_RECEIVER, ARG_RECIPIENT, ARG_RECIPIENTS,
+        ARG_ROLE, ARG_SIGNATURE, ARG_SNAPSHOT_ID, ARG_SPENDER, ARG_START, ARG_SYMBOL,
+        ARG_TIMESTAMP, ARG_TOTAL_SUPPLY, ARG_UPGRADE, ARG_WRAPPED, DECIMALS_KEY, NAME_KEY,
+        SYMBOL_KEY,
     },
     detail::{read_from, ret},
+    host::CasperHost,
 };
 
 #[no_mangle]
 pub extern "C" fn name() {
-    let val: String = read_from(NAME_KEY);
+    let val: String = read_from(&CasperHost, NAME_KEY);
     ret(val)
 }
 
 #[no_mangle]
 pub extern "C" fn symbol() {
-    let val: String = read_from(SYMBOL_KEY);
+    let val: String = read_from(&CasperHost, SYMBOL_KEY);
     ret(val)
 }
 
 #[no_mangle]
 pub extern "C" fn decimals() {
-    let val: u8 = read_from(DECIMALS_KEY);
+    let val: u8 = read_from(&CasperHost, DECIMALS_KEY);
     ret(val)
 }
 
 #[no_mangle]
 pub extern "C" fn total_supply() {
-    let val: U512 = read_from(TOTAL_SUPPLY_KEY);
+    let val: TokenAmount = erc20::total_supply(&CasperHost);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn cap() {
+    let val: Option<TokenAmount> = erc20::cap(&CasperHost);
     ret(val)
 }
 
 #[no_mangle]
 pub extern "C" fn balance_of() {
-    let address: AccountHash = runtime::get_named_arg(ARG_ADDRESS);
-    let val = erc20::balance_of(address);
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let val = erc20::balance_of(&CasperHost, address);
     ret(val)
 }
 
 #[no_mangle]
 pub extern "C" fn transfer() {
-    let recipient: AccountHash = runtime::get_named_arg(ARG_RECIPIENT);
-    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let recipient: Address = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+
+    erc20::transfer(&CasperHost, &recipient, amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn batch_transfer() {
+    let recipients: Vec<Address> = runtime::get_named_arg(ARG_RECIPIENTS);
+    let amounts: Vec<TokenAmount> = runtime::get_named_arg(ARG_AMOUNTS);
 
-    erc20::transfer(&recipient, amount).unwrap_or_revert();
+    erc20::batch_transfer(&CasperHost, recipients, amounts).unwrap_or_revert();
 }
 
 #[no_mangle]
 pub extern "C" fn approve() {
-    let spender: AccountHash = runtime::get_named_arg(ARG_SPENDER);
-    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let spender: Address = runtime::get_named_arg(ARG_SPENDER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+
+    erc20::approve(&CasperHost, spender, amount).unwrap_or_revert();
+}
 
-    erc20::approve(spender, amount).unwrap_or_revert();
+#[no_mangle]
+pub extern "C" fn transfer_and_call() {
+    let recipient: ContractPackageHash = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let data: Vec<u8> = runtime::get_named_arg(ARG_DATA);
+    erc20::transfer_and_call(&CasperHost, recipient, amount, data).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn approve_and_call() {
+    let spender: ContractPackageHash = runtime::get_named_arg(ARG_SPENDER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let data: Vec<u8> = runtime::get_named_arg(ARG_DATA);
+    erc20::approve_and_call(&CasperHost, spender, amount, data).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn approve_with_expiry() {
+    let spender: Address = runtime::get_named_arg(ARG_SPENDER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let expires_at: u64 = runtime::get_named_arg(ARG_EXPIRES_AT);
+    erc20::approve_with_expiry(&CasperHost, spender, amount, expires_at).unwrap_or_revert();
 }
 
 #[no_mangle]
 pub extern "C" fn allowance() {
-    let owner: AccountHash = runtime::get_named_arg(ARG_OWNER);
-    let spender: AccountHash = runtime::get_named_arg(ARG_SPENDER);
-    let val = erc20::allowance(owner, spender);
+    let owner: Address = runtime::get_named_arg(ARG_OWNER);
+    let spender: Address = runtime::get_named_arg(ARG_SPENDER);
+    let val = erc20::allowance(&CasperHost, owner, spender);
     ret(val)
 }
 
 #[no_mangle]
 pub extern "C" fn transfer_from() {
-    let owner: AccountHash = runtime::get_named_arg(ARG_OWNER);
-    let recipient: AccountHash = runtime::get_named_arg(ARG_RECIPIENT);
-    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
-    erc20::transfer_from(owner, recipient, amount).unwrap_or_revert();
+    let owner: Address = runtime::get_named_arg(ARG_OWNER);
+    let recipient: Address = runtime::get_named_arg(ARG_RECIPIENT);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    erc20::transfer_from(&CasperHost, owner, recipient, amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn increase_allowance() {
+    let spender: Address = runtime::get_named_arg(ARG_SPENDER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+
+    erc20::increase_allowance(&CasperHost, spender, amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn decrease_allowance() {
+    let spender: Address = runtime::get_named_arg(ARG_SPENDER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+
+    erc20::decrease_allowance(&CasperHost, spender, amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn grant_role() {
+    let role: String = runtime::get_named_arg(ARG_ROLE);
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    erc20::grant_role(&CasperHost, role, address).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn revoke_role() {
+    let role: String = runtime::get_named_arg(ARG_ROLE);
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    erc20::revoke_role(&CasperHost, role, address).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn renounce_role() {
+    let role: String = runtime::get_named_arg(ARG_ROLE);
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    erc20::renounce_role(&CasperHost, role, address).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn has_role() {
+    let role: String = runtime::get_named_arg(ARG_ROLE);
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let val = erc20::has_role(&CasperHost, role, address);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn owner() {
+    let val = erc20::owner(&CasperHost);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_ownership() {
+    let new_owner: Address = runtime::get_named_arg(ARG_NEW_OWNER);
+    erc20::transfer_ownership(&CasperHost, new_owner).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn accept_ownership() {
+    erc20::accept_ownership(&CasperHost).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn renounce_ownership() {
+    erc20::renounce_ownership(&CasperHost).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn pause() {
+    erc20::pause(&CasperHost).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn unpause() {
+    erc20::unpause(&CasperHost).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn permit() {
+    let owner_public_key: PublicKey = runtime::get_named_arg(ARG_OWNER_PUBLIC_KEY);
+    let spender: Address = runtime::get_named_arg(ARG_SPENDER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let deadline: u64 = runtime::get_named_arg(ARG_DEADLINE);
+    let nonce: u64 = runtime::get_named_arg(ARG_NONCE);
+    let signature: Vec<u8> = runtime::get_named_arg(ARG_SIGNATURE);
+
+    erc20::permit(
+        &CasperHost,
+        owner_public_key,
+        spender,
+        amount,
+        deadline,
+        nonce,
+        signature,
+    )
+    .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn nonces() {
+    let owner: Address = runtime::get_named_arg(ARG_OWNER);
+    let val = erc20::nonces(&CasperHost, owner);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn snapshot() {
+    let val = erc20::snapshot(&CasperHost).unwrap_or_revert();
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of_at() {
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let snapshot_id: u64 = runtime::get_named_arg(ARG_SNAPSHOT_ID);
+    let val = erc20::balance_of_at(&CasperHost, address, snapshot_id).unwrap_or_revert();
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn total_supply_at() {
+    let snapshot_id: u64 = runtime::get_named_arg(ARG_SNAPSHOT_ID);
+    let val = erc20::total_supply_at(&CasperHost, snapshot_id).unwrap_or_revert();
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn delegate() {
+    let delegatee: Address = runtime::get_named_arg(ARG_DELEGATEE);
+    erc20::delegate_votes(&CasperHost, delegatee).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn delegates() {
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let val = erc20::delegates(&CasperHost, address);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn get_votes() {
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let val = erc20::get_votes(&CasperHost, address);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn get_past_votes() {
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let timestamp: u64 = runtime::get_named_arg(ARG_TIMESTAMP);
+    let val = erc20::get_past_votes(&CasperHost, address, timestamp).unwrap_or_revert();
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn freeze() {
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    erc20::freeze(&CasperHost, address).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn unfreeze() {
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    erc20::unfreeze(&CasperHost, address).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn is_frozen() {
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let val = erc20::is_frozen(&CasperHost, address);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn set_transfer_fee() {
+    let fee_basis_points: u32 = runtime::get_named_arg(ARG_FEE_BASIS_POINTS);
+    let fee_recipient: Address = runtime::get_named_arg(ARG_FEE_RECIPIENT);
+    erc20::set_transfer_fee(&CasperHost, fee_basis_points, fee_recipient).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_fee_exempt() {
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let exempt: bool = runtime::get_named_arg(ARG_EXEMPT);
+    erc20::set_fee_exempt(&CasperHost, address, exempt).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn fee_basis_points() {
+    let val = erc20::fee_basis_points(&CasperHost);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn fee_recipient() {
+    let val = erc20::fee_recipient(&CasperHost);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn is_fee_exempt() {
+    let address: Address = runtime::get_named_arg(ARG_ADDRESS);
+    let val = erc20::is_fee_exempt(&CasperHost, address);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn create_vesting_schedule() {
+    let beneficiary: Address = runtime::get_named_arg(ARG_BENEFICIARY);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let start: u64 = runtime::get_named_arg(ARG_START);
+    let cliff: u64 = runtime::get_named_arg(ARG_CLIFF);
+    let duration: u64 = runtime::get_named_arg(ARG_DURATION);
+    erc20::create_vesting_schedule(&CasperHost, beneficiary, amount, start, cliff, duration)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn release() {
+    let val = erc20::release(&CasperHost).unwrap_or_revert();
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn vested_amount() {
+    let beneficiary: Address = runtime::get_named_arg(ARG_BENEFICIARY);
+    let val = erc20::vested_amount(&CasperHost, beneficiary).unwrap_or_revert();
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn releasable_amount() {
+    let beneficiary: Address = runtime::get_named_arg(ARG_BENEFICIARY);
+    let val = erc20::releasable_amount(&CasperHost, beneficiary).unwrap_or_revert();
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn max_flash_loan() {
+    let val = erc20::max_flash_loan(&CasperHost);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn flash_fee() {
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let val = erc20::flash_fee(amount);
+    ret(val)
+}
+
+#[no_mangle]
+pub extern "C" fn flash_loan() {
+    let receiver: ContractPackageHash = runtime::get_named_arg(ARG_RECEIVER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    let data: Vec<u8> = runtime::get_named_arg(ARG_DATA);
+    erc20::flash_loan(&CasperHost, receiver, amount, data).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn deposit() {
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    erc20::deposit(&CasperHost, purse, amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn withdraw() {
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    erc20::withdraw(&CasperHost, amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn mint() {
+    let owner: Address = runtime::get_named_arg(ARG_OWNER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    erc20::mint(&CasperHost, owner, amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn burn() {
+    let owner: Address = runtime::get_named_arg(ARG_OWNER);
+    let amount: TokenAmount = runtime::get_named_arg(ARG_AMOUNT);
+    erc20::burn(&CasperHost, owner, amount).unwrap_or_revert();
 }
 
 #[no_mangle]
 fn call() {
     let name: String = runtime::get_named_arg(ARG_NAME);
+    let mintable = runtime::get_named_arg(ARG_MINTABLE);
+    let upgrade: bool = runtime::get_named_arg(ARG_UPGRADE);
+
+    if upgrade {
+        // Token with this name was installed by the caller, so a new version of it is added instead.
+        erc20::upgrade(&name, mintable).unwrap_or_revert();
+        return;
+    }
+
     let symbol: String = runtime::get_named_arg(ARG_SYMBOL);
     let decimals = runtime::get_named_arg(ARG_DECIMALS);
     let total_supply = runtime::get_named_arg(ARG_TOTAL_SUPPLY);
+    let cap = runtime::get_named_arg(ARG_CAP);
+    let wrapped = runtime::get_named_arg(ARG_WRAPPED);
 
-    erc20::delegate(name, symbol, decimals, total_supply).unwrap_or_revert();
+    erc20::delegate(name, symbol, decimals, total_supply, mintable, wrapped, cap)
+        .unwrap_or_revert();
 }
@@ -6,14 +6,60 @@ pub const NAME_KEY: &str = "name";
 pub const SYMBOL_KEY: &str = "symbol";
 /// Named constant of `decimals`
 pub const DECIMALS_KEY: &str = "decimals";
-/// Named constant of `contract`
+/// Named constant of `contract`, prefixed with the token name in the installer's named keys.
 pub const CONTRACT_KEY: &str = "contract";
+/// Named constant of `contract_package`, prefixed with the token name in the installer's named keys.
+pub const CONTRACT_PACKAGE_KEY: &str = "contract_package";
+/// Named constant of `contract_access`, prefixed with the token name in the installer's named keys.
+pub const CONTRACT_ACCESS_KEY: &str = "contract_access";
+/// Named constant of `contract_wrapped`, prefixed with the token name in the installer's named keys.
+pub const CONTRACT_WRAPPED_KEY: &str = "contract_wrapped";
 /// Named constant of `balances`
 pub const BALANCES_KEY: &str = "balances";
 /// Named constant of `allowances`
 pub const ALLOWANCES_KEY: &str = "allowances";
 /// Named constant of `total_supply`
 pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
+/// Named constant of `events`
+pub const EVENTS_KEY: &str = "events";
+/// Named constant of `event_count`
+pub const EVENT_COUNT_KEY: &str = "event_count";
+/// Named constant of `roles`
+pub const ROLES_KEY: &str = "roles";
+/// Named constant of `role_admins`
+pub const ROLE_ADMINS_KEY: &str = "role_admins";
+/// Named constant of `owner`
+pub const OWNER_KEY: &str = "owner";
+/// Named constant of `pending_owner`
+pub const PENDING_OWNER_KEY: &str = "pending_owner";
+/// Named constant of `cap`
+pub const CAP_KEY: &str = "cap";
+/// Named constant of `nonces`
+pub const NONCES_KEY: &str = "nonces";
+/// Named constant of `paused`
+pub const PAUSED_KEY: &str = "paused";
+/// Named constant of `snapshot_id`
+pub const SNAPSHOT_ID_KEY: &str = "snapshot_id";
+/// Named constant of `account_snapshots`
+pub const ACCOUNT_SNAPSHOTS_KEY: &str = "account_snapshots";
+/// Named constant of `total_supply_snapshots`
+pub const TOTAL_SUPPLY_SNAPSHOTS_KEY: &str = "total_supply_snapshots";
+/// Named constant of `delegates`
+pub const DELEGATES_KEY: &str = "delegates";
+/// Named constant of `vote_checkpoints`
+pub const VOTE_CHECKPOINTS_KEY: &str = "vote_checkpoints";
+/// Named constant of `frozen_accounts`
+pub const FROZEN_ACCOUNTS_KEY: &str = "frozen_accounts";
+/// Named constant of `fee_basis_points`
+pub const FEE_BASIS_POINTS_KEY: &str = "fee_basis_points";
+/// Named constant of `fee_recipient`
+pub const FEE_RECIPIENT_KEY: &str = "fee_recipient";
+/// Named constant of `fee_exemptions`
+pub const FEE_EXEMPTIONS_KEY: &str = "fee_exemptions";
+/// Named constant of `vesting_schedules`
+pub const VESTING_SCHEDULES_KEY: &str = "vesting_schedules";
+/// Named constant of `purse`
+pub const PURSE_KEY: &str = "purse";
 
 /// Named constant for method `name`.
 pub const METHOD_NAME: &str = "name";
@@ -33,6 +79,102 @@ pub const METHOD_ALLOWANCE: &str = "allowance";
 pub const METHOD_TRANSFER_FROM: &str = "transfer_from";
 /// Named constant for method `total_supply`.
 pub const METHOD_TOTAL_SUPPLY: &str = "total_supply";
+/// Named constant for method `increase_allowance`.
+pub const METHOD_INCREASE_ALLOWANCE: &str = "increase_allowance";
+/// Named constant for method `decrease_allowance`.
+pub const METHOD_DECREASE_ALLOWANCE: &str = "decrease_allowance";
+/// Named constant for method `pause`.
+pub const METHOD_PAUSE: &str = "pause";
+/// Named constant for method `unpause`.
+pub const METHOD_UNPAUSE: &str = "unpause";
+/// Named constant for method `grant_role`.
+pub const METHOD_GRANT_ROLE: &str = "grant_role";
+/// Named constant for method `revoke_role`.
+pub const METHOD_REVOKE_ROLE: &str = "revoke_role";
+/// Named constant for method `renounce_role`.
+pub const METHOD_RENOUNCE_ROLE: &str = "renounce_role";
+/// Named constant for method `has_role`.
+pub const METHOD_HAS_ROLE: &str = "has_role";
+/// Named constant for method `owner`.
+pub const METHOD_OWNER: &str = "owner";
+/// Named constant for method `transfer_ownership`.
+pub const METHOD_TRANSFER_OWNERSHIP: &str = "transfer_ownership";
+/// Named constant for method `accept_ownership`.
+pub const METHOD_ACCEPT_OWNERSHIP: &str = "accept_ownership";
+/// Named constant for method `renounce_ownership`.
+pub const METHOD_RENOUNCE_OWNERSHIP: &str = "renounce_ownership";
+/// Named constant for method `cap`.
+pub const METHOD_CAP: &str = "cap";
+/// Named constant for method `permit`.
+pub const METHOD_PERMIT: &str = "permit";
+/// Named constant for method `nonces`.
+pub const METHOD_NONCES: &str = "nonces";
+/// Named constant for method `snapshot`.
+pub const METHOD_SNAPSHOT: &str = "snapshot";
+/// Named constant for method `balance_of_at`.
+pub const METHOD_BALANCE_OF_AT: &str = "balance_of_at";
+/// Named constant for method `total_supply_at`.
+pub const METHOD_TOTAL_SUPPLY_AT: &str = "total_supply_at";
+/// Named constant for method `delegate`.
+pub const METHOD_DELEGATE: &str = "delegate";
+/// Named constant for method `delegates`.
+pub const METHOD_DELEGATES: &str = "delegates";
+/// Named constant for method `get_votes`.
+pub const METHOD_GET_VOTES: &str = "get_votes";
+/// Named constant for method `get_past_votes`.
+pub const METHOD_GET_PAST_VOTES: &str = "get_past_votes";
+/// Named constant for method `freeze`.
+pub const METHOD_FREEZE: &str = "freeze";
+/// Named constant for method `unfreeze`.
+pub const METHOD_UNFREEZE: &str = "unfreeze";
+/// Named constant for method `is_frozen`.
+pub const METHOD_IS_FROZEN: &str = "is_frozen";
+/// Named constant for method `set_transfer_fee`.
+pub const METHOD_SET_TRANSFER_FEE: &str = "set_transfer_fee";
+/// Named constant for method `set_fee_exempt`.
+pub const METHOD_SET_FEE_EXEMPT: &str = "set_fee_exempt";
+/// Named constant for method `fee_basis_points`.
+pub const METHOD_FEE_BASIS_POINTS: &str = "fee_basis_points";
+/// Named constant for method `fee_recipient`.
+pub const METHOD_FEE_RECIPIENT: &str = "fee_recipient";
+/// Named constant for method `is_fee_exempt`.
+pub const METHOD_IS_FEE_EXEMPT: &str = "is_fee_exempt";
+/// Named constant for method `create_vesting_schedule`.
+pub const METHOD_CREATE_VESTING_SCHEDULE: &str = "create_vesting_schedule";
+/// Named constant for method `release`.
+pub const METHOD_RELEASE: &str = "release";
+/// Named constant for method `vested_amount`.
+pub const METHOD_VESTED_AMOUNT: &str = "vested_amount";
+/// Named constant for method `releasable_amount`.
+pub const METHOD_RELEASABLE_AMOUNT: &str = "releasable_amount";
+/// Named constant for method `batch_transfer`.
+pub const METHOD_BATCH_TRANSFER: &str = "batch_transfer";
+/// Named constant for method `max_flash_loan`.
+pub const METHOD_MAX_FLASH_LOAN: &str = "max_flash_loan";
+/// Named constant for method `flash_fee`.
+pub const METHOD_FLASH_FEE: &str = "flash_fee";
+/// Named constant for method `flash_loan`.
+pub const METHOD_FLASH_LOAN: &str = "flash_loan";
+/// Named constant for method `on_flash_loan` of a flash loan receiver.
+pub const METHOD_ON_FLASH_LOAN: &str = "on_flash_loan";
+/// Named constant for method `deposit`.
+pub const METHOD_DEPOSIT: &str = "deposit";
+/// Named constant for method `withdraw`.
+pub const METHOD_WITHDRAW: &str = "withdraw";
+/// Named constant for method `transfer_and_call`.
+pub const METHOD_TRANSFER_AND_CALL: &str = "transfer_and_call";
+/// Named constant for method `approve_and_call`.
+pub const METHOD_APPROVE_AND_CALL: &str = "approve_and_call";
+/// Named constant for method `approve_with_expiry`.
+pub const METHOD_APPROVE_WITH_EXPIRY: &str = "approve_with_expiry";
+/// Named constant for method `on_transfer_received` of a recipient contract.
+pub const METHOD_ON_TRANSFER_RECEIVED: &str = "on_transfer_received";
+/// Named constant for method `on_approval_received` of a spender contract.
+pub const METHOD_ON_APPROVAL_RECEIVED: &str = "on_approval_received";
+/// Named constant for method `mint`.
+pub const METHOD_MINT: &str = "mint";
+/// Named constant for method `burn`.
+pub const METHOD_BURN: &str = "burn";
 
 /// Named constant for `address`.
 pub const ARG_ADDRESS: &str = "address";
@@ -52,3 +194,63 @@ pub const ARG_SYMBOL: &str = "symbol";
 pub const ARG_DECIMALS: &str = "decimals";
 /// Named constant for `total_supply`.
 pub const ARG_TOTAL_SUPPLY: &str = "total_supply";
+/// Named constant for `mintable`.
+pub const ARG_MINTABLE: &str = "mintable";
+/// Named constant for `wrapped`.
+pub const ARG_WRAPPED: &str = "wrapped";
+/// Named constant for `upgrade`.
+pub const ARG_UPGRADE: &str = "upgrade";
+/// Named constant for `role`.
+pub const ARG_ROLE: &str = "role";
+/// Named constant for `new_owner`.
+pub const ARG_NEW_OWNER: &str = "new_owner";
+/// Named constant for `cap`.
+pub const ARG_CAP: &str = "cap";
+/// Named constant for `owner_public_key`.
+pub const ARG_OWNER_PUBLIC_KEY: &str = "owner_public_key";
+/// Named constant for `deadline`.
+pub const ARG_DEADLINE: &str = "deadline";
+/// Named constant for `nonce`.
+pub const ARG_NONCE: &str = "nonce";
+/// Named constant for `signature`.
+pub const ARG_SIGNATURE: &str = "signature";
+/// Named constant for `snapshot_id`.
+pub const ARG_SNAPSHOT_ID: &str = "snapshot_id";
+/// Named constant for `delegatee`.
+pub const ARG_DELEGATEE: &str = "delegatee";
+/// Named constant for `timestamp`.
+pub const ARG_TIMESTAMP: &str = "timestamp";
+/// Named constant for `fee_basis_points`.
+pub const ARG_FEE_BASIS_POINTS: &str = "fee_basis_points";
+/// Named constant for `fee_recipient`.
+pub const ARG_FEE_RECIPIENT: &str = "fee_recipient";
+/// Named constant for `exempt`.
+pub const ARG_EXEMPT: &str = "exempt";
+/// Named constant for `beneficiary`.
+pub const ARG_BENEFICIARY: &str = "beneficiary";
+/// Named constant for `start`.
+pub const ARG_START: &str = "start";
+/// Named constant for `cliff`.
+pub const ARG_CLIFF: &str = "cliff";
+/// Named constant for `duration`.
+pub const ARG_DURATION: &str = "duration";
+/// Named constant for `recipients`.
+pub const ARG_RECIPIENTS: &str = "recipients";
+/// Named constant for `amounts`.
+pub const ARG_AMOUNTS: &str = "amounts";
+/// Named constant for `receiver`.
+pub const ARG_RECEIVER: &str = "receiver";
+/// Named constant for `data`.
+pub const ARG_DATA: &str = "data";
+/// Named constant for `initiator`.
+pub const ARG_INITIATOR: &str = "initiator";
+/// Named constant for `fee`.
+pub const ARG_FEE: &str = "fee";
+/// Named constant for `purse`.
+pub const ARG_PURSE: &str = "purse";
+/// Named constant for `operator`.
+pub const ARG_OPERATOR: &str = "operator";
+/// Named constant for `from`.
+pub const ARG_FROM: &str = "from";
+/// Named constant for `expires_at`.
+pub const ARG_EXPIRES_AT: &str = "expires_at";
@@ -1,63 +1,63 @@
 //! Implementation details.
-use core::convert::TryInto;
+use alloc::string::String;
 
-use casper_contract::{
-    contract_api::{runtime, storage},
-    unwrap_or_revert::UnwrapOrRevert,
-};
+#[cfg(feature = "casper")]
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
 use casper_types::{
-    account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
-    system::CallStackElement,
-    CLTyped, CLValue, URef,
+    CLTyped,
 };
+#[cfg(feature = "casper")]
+use casper_types::{system::CallStackElement, CLValue};
 
-use crate::error::Error;
+use crate::{
+    address::Address,
+    constants::OWNER_KEY,
+    error::Error,
+    host::{Host, UnwrapOrRevert as _},
+};
 
 /// Shortcut for `runtime::ret`
+#[cfg(feature = "casper")]
 #[inline]
 pub fn ret<T: CLTyped + ToBytes>(value: T) {
     runtime::ret(CLValue::from_t(value).unwrap_or_revert())
 }
 
-/// Gets [`URef`] under a name.
-pub fn get_uref(name: &str) -> URef {
-    let key = runtime::get_key(name).unwrap_or_revert();
-    key.try_into().unwrap_or_revert()
-}
-
 /// Reads value from a named key.
-pub fn read_from<T>(name: &str) -> T
+pub fn read_from<H, T>(host: &H, name: &str) -> T
 where
+    H: Host,
     T: FromBytes + CLTyped,
 {
-    let uref = get_uref(name);
-    let value: T = storage::read(uref).unwrap_or_revert().unwrap_or_revert();
+    let uref = host.get_uref(name);
+    let value: T = host.read(uref).unwrap_or_revert_on(host);
     value
 }
 
-/// Gets the immediate call stack element of the current execution.
-fn get_immediate_call_stack_item() -> Option<CallStackElement> {
-    let call_stack = runtime::get_call_stack();
-    call_stack.into_iter().rev().nth(1)
+/// Creates a dictionary item key for an [`Address`].
+///
+/// Serialized [`Address`] contains a tag of the variant, so an account and a contract package with the same hash never share an item key.
+pub fn make_dictionary_item_key<H: Host>(host: &H, address: &Address) -> String {
+    let preimage = address.to_bytes().unwrap_or_revert_on(host);
+    let key_bytes = host.blake2b(&preimage);
+    hex::encode(&key_bytes)
 }
 
-/// Gets the immediate caller of the current execution.
+/// Creates a name of a named key kept by the installer of a token.
 ///
-/// This function ensures that only session code can execute this function, and disallows stored session/stored contracts.
-#[inline]
-pub fn get_immediate_caller() -> Result<AccountHash, Error> {
-    match get_immediate_call_stack_item() {
-        Some(CallStackElement::Session { account_hash }) => Ok(account_hash),
-        Some(CallStackElement::StoredSession { .. })
-        | Some(CallStackElement::StoredContract { .. })
-        | None => Err(Error::InvalidContext),
-    }
+/// Name is prefixed with the `token_name`, so tokens installed by the same account don't share named keys.
+pub fn make_installer_key(token_name: &str, key: &str) -> String {
+    let mut installer_key = String::from(token_name);
+    installer_key.push('_');
+    installer_key.push_str(key);
+    installer_key
 }
 
 /// This function makes sure that the contract is called directly through a deploy.
 ///
 /// An attempt to call this function from within a stored contract will fail with [`Error::InvalidContext`].
+#[cfg(feature = "casper")]
 #[inline]
 pub fn requires_session_code() -> Result<(), Error> {
     let call_stack = runtime::get_call_stack();
@@ -69,3 +69,18 @@ pub fn requires_session_code() -> Result<(), Error> {
         Err(Error::InvalidContext)
     }
 }
+
+/// This function makes sure that the immediate caller is the owner of the contract.
+///
+/// An attempt to call this function by any other address, or after the ownership was renounced, will fail with [`Error::Unauthorized`].
+#[inline]
+pub fn only_owner<H: Host>(host: &H) -> Result<(), Error> {
+    let caller = host.get_immediate_caller()?;
+    let owner: Option<Address> = read_from(host, OWNER_KEY);
+
+    if owner == Some(caller) {
+        Ok(())
+    } else {
+        Err(Error::Unauthorized)
+    }
+}
@@ -2,20 +2,37 @@
 use alloc::vec;
 use alloc::{string::String, vec::Vec};
 
-use casper_types::U512;
 use casper_types::{
-    account::AccountHash, CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType,
-    EntryPoints, Parameter,
+    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
 };
+use casper_types::{ContractPackageHash, PublicKey, URef};
 
 use crate::constants::{
-    ARG_ADDRESS, ARG_AMOUNT, ARG_OWNER, ARG_RECIPIENT, ARG_SPENDER, METHOD_ALLOWANCE,
-    METHOD_APPROVE, METHOD_BALANCE_OF, METHOD_DECIMALS, METHOD_NAME, METHOD_SYMBOL,
-    METHOD_TOTAL_SUPPLY, METHOD_TRANSFER, METHOD_TRANSFER_FROM,
+    ARG_ADDRESS, ARG_AMOUNT, ARG_AMOUNTS, ARG_BENEFICIARY, ARG_CLIFF, ARG_DATA, ARG_DEADLINE,
+    ARG_DELEGATEE, ARG_DURATION, ARG_EXEMPT, ARG_EXPIRES_AT, ARG_FEE_BASIS_POINTS,
+    ARG_FEE_RECIPIENT, ARG_NEW_OWNER, ARG_NONCE, ARG_OWNER, ARG_OWNER_PUBLIC_KEY, ARG_PURSE,
+    ARG_RECEIVER, ARG_RECIPIENT, ARG_RECIPIENTS, ARG_ROLE, ARG_SIGNATURE, ARG_SNAPSHOT_ID,
+    ARG_SPENDER, ARG_START, ARG_TIMESTAMP, METHOD_ACCEPT_OWNERSHIP, METHOD_ALLOWANCE,
+    METHOD_APPROVE, METHOD_APPROVE_AND_CALL, METHOD_APPROVE_WITH_EXPIRY, METHOD_BALANCE_OF,
+    METHOD_BALANCE_OF_AT, METHOD_BATCH_TRANSFER, METHOD_BURN, METHOD_CAP,
+    METHOD_CREATE_VESTING_SCHEDULE, METHOD_DECIMALS, METHOD_DECREASE_ALLOWANCE, METHOD_DELEGATE,
+    METHOD_DELEGATES, METHOD_DEPOSIT, METHOD_FEE_BASIS_POINTS, METHOD_FEE_RECIPIENT,
+    METHOD_FLASH_FEE, METHOD_FLASH_LOAN, METHOD_FREEZE, METHOD_GET_PAST_VOTES, METHOD_GET_VOTES,
+    METHOD_GRANT_ROLE, METHOD_HAS_ROLE, METHOD_INCREASE_ALLOWANCE, METHOD_IS_FEE_EXEMPT,
+    METHOD_IS_FROZEN, METHOD_MAX_FLASH_LOAN, METHOD_MINT, METHOD_NAME, METHOD_NONCES, METHOD_OWNER,
+    METHOD_PAUSE, METHOD_PERMIT, METHOD_RELEASABLE_AMOUNT, METHOD_RELEASE,
+    METHOD_RENOUNCE_OWNERSHIP, METHOD_RENOUNCE_ROLE, METHOD_REVOKE_ROLE, METHOD_SET_FEE_EXEMPT,
+    METHOD_SET_TRANSFER_FEE, METHOD_SNAPSHOT, METHOD_SYMBOL, METHOD_TOTAL_SUPPLY,
+    METHOD_TOTAL_SUPPLY_AT, METHOD_TRANSFER, METHOD_TRANSFER_AND_CALL, METHOD_TRANSFER_FROM,
+    METHOD_TRANSFER_OWNERSHIP, METHOD_UNFREEZE, METHOD_UNPAUSE, METHOD_VESTED_AMOUNT,
+    METHOD_WITHDRAW,
 };
+use crate::{address::Address, amount::TokenAmount};
 
 /// Returns entry points for an erc20 token.
-pub fn get_entry_points() -> EntryPoints {
+///
+/// Entry points `mint` and `burn` are included only if the token is `mintable`, and entry points `deposit` and `withdraw` only if the token is `wrapped`.
+pub fn get_entry_points(mintable: bool, wrapped: bool) -> EntryPoints {
     let mut entry_points = EntryPoints::new();
     let name_entry_point = EntryPoint::new(
         String::from(METHOD_NAME),
@@ -44,15 +61,23 @@ pub fn get_entry_points() -> EntryPoints {
     let total_supply_entry_point = EntryPoint::new(
         String::from(METHOD_TOTAL_SUPPLY),
         Vec::new(),
-        U512::cl_type(),
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let cap_entry_point = EntryPoint::new(
+        String::from(METHOD_CAP),
+        Vec::new(),
+        Option::<TokenAmount>::cl_type(),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     );
 
     let balance_of_entry_point = EntryPoint::new(
         String::from(METHOD_BALANCE_OF),
-        vec![Parameter::new(ARG_ADDRESS, AccountHash::cl_type())],
-        U512::cl_type(),
+        vec![Parameter::new(ARG_ADDRESS, Address::cl_type())],
+        TokenAmount::cl_type(),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     );
@@ -60,8 +85,19 @@ pub fn get_entry_points() -> EntryPoints {
     let transfer_entry_point = EntryPoint::new(
         String::from(METHOD_TRANSFER),
         vec![
-            Parameter::new(ARG_RECIPIENT, AccountHash::cl_type()),
-            Parameter::new(ARG_AMOUNT, U512::cl_type()),
+            Parameter::new(ARG_RECIPIENT, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let batch_transfer_entry_point = EntryPoint::new(
+        String::from(METHOD_BATCH_TRANSFER),
+        vec![
+            Parameter::new(ARG_RECIPIENTS, Vec::<Address>::cl_type()),
+            Parameter::new(ARG_AMOUNTS, Vec::<TokenAmount>::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -71,8 +107,8 @@ pub fn get_entry_points() -> EntryPoints {
     let approve_entry_point = EntryPoint::new(
         String::from(METHOD_APPROVE),
         vec![
-            Parameter::new(ARG_SPENDER, AccountHash::cl_type()),
-            Parameter::new(ARG_AMOUNT, U512::cl_type()),
+            Parameter::new(ARG_SPENDER, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -82,10 +118,10 @@ pub fn get_entry_points() -> EntryPoints {
     let allowance_entry_point = EntryPoint::new(
         String::from(METHOD_ALLOWANCE),
         vec![
-            Parameter::new(ARG_OWNER, AccountHash::cl_type()),
-            Parameter::new(ARG_SPENDER, AccountHash::cl_type()),
+            Parameter::new(ARG_OWNER, Address::cl_type()),
+            Parameter::new(ARG_SPENDER, Address::cl_type()),
         ],
-        U512::cl_type(),
+        TokenAmount::cl_type(),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     );
@@ -93,23 +129,487 @@ pub fn get_entry_points() -> EntryPoints {
     let transfer_from_entry_point = EntryPoint::new(
         String::from(METHOD_TRANSFER_FROM),
         vec![
-            Parameter::new(ARG_OWNER, AccountHash::cl_type()),
-            Parameter::new(ARG_RECIPIENT, AccountHash::cl_type()),
-            Parameter::new(ARG_AMOUNT, U512::cl_type()),
+            Parameter::new(ARG_OWNER, Address::cl_type()),
+            Parameter::new(ARG_RECIPIENT, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let permit_entry_point = EntryPoint::new(
+        String::from(METHOD_PERMIT),
+        vec![
+            Parameter::new(ARG_OWNER_PUBLIC_KEY, PublicKey::cl_type()),
+            Parameter::new(ARG_SPENDER, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            Parameter::new(ARG_DEADLINE, u64::cl_type()),
+            Parameter::new(ARG_NONCE, u64::cl_type()),
+            Parameter::new(ARG_SIGNATURE, Vec::<u8>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let nonces_entry_point = EntryPoint::new(
+        String::from(METHOD_NONCES),
+        vec![Parameter::new(ARG_OWNER, Address::cl_type())],
+        u64::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let snapshot_entry_point = EntryPoint::new(
+        String::from(METHOD_SNAPSHOT),
+        Vec::new(),
+        u64::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let balance_of_at_entry_point = EntryPoint::new(
+        String::from(METHOD_BALANCE_OF_AT),
+        vec![
+            Parameter::new(ARG_ADDRESS, Address::cl_type()),
+            Parameter::new(ARG_SNAPSHOT_ID, u64::cl_type()),
+        ],
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let total_supply_at_entry_point = EntryPoint::new(
+        String::from(METHOD_TOTAL_SUPPLY_AT),
+        vec![Parameter::new(ARG_SNAPSHOT_ID, u64::cl_type())],
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let delegate_entry_point = EntryPoint::new(
+        String::from(METHOD_DELEGATE),
+        vec![Parameter::new(ARG_DELEGATEE, Address::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let delegates_entry_point = EntryPoint::new(
+        String::from(METHOD_DELEGATES),
+        vec![Parameter::new(ARG_ADDRESS, Address::cl_type())],
+        Option::<Address>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let get_votes_entry_point = EntryPoint::new(
+        String::from(METHOD_GET_VOTES),
+        vec![Parameter::new(ARG_ADDRESS, Address::cl_type())],
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let get_past_votes_entry_point = EntryPoint::new(
+        String::from(METHOD_GET_PAST_VOTES),
+        vec![
+            Parameter::new(ARG_ADDRESS, Address::cl_type()),
+            Parameter::new(ARG_TIMESTAMP, u64::cl_type()),
+        ],
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let freeze_entry_point = EntryPoint::new(
+        String::from(METHOD_FREEZE),
+        vec![Parameter::new(ARG_ADDRESS, Address::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let unfreeze_entry_point = EntryPoint::new(
+        String::from(METHOD_UNFREEZE),
+        vec![Parameter::new(ARG_ADDRESS, Address::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let is_frozen_entry_point = EntryPoint::new(
+        String::from(METHOD_IS_FROZEN),
+        vec![Parameter::new(ARG_ADDRESS, Address::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let set_transfer_fee_entry_point = EntryPoint::new(
+        String::from(METHOD_SET_TRANSFER_FEE),
+        vec![
+            Parameter::new(ARG_FEE_BASIS_POINTS, u32::cl_type()),
+            Parameter::new(ARG_FEE_RECIPIENT, Address::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let set_fee_exempt_entry_point = EntryPoint::new(
+        String::from(METHOD_SET_FEE_EXEMPT),
+        vec![
+            Parameter::new(ARG_ADDRESS, Address::cl_type()),
+            Parameter::new(ARG_EXEMPT, bool::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let fee_basis_points_entry_point = EntryPoint::new(
+        String::from(METHOD_FEE_BASIS_POINTS),
+        Vec::new(),
+        u32::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let fee_recipient_entry_point = EntryPoint::new(
+        String::from(METHOD_FEE_RECIPIENT),
+        Vec::new(),
+        Option::<Address>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let is_fee_exempt_entry_point = EntryPoint::new(
+        String::from(METHOD_IS_FEE_EXEMPT),
+        vec![Parameter::new(ARG_ADDRESS, Address::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let create_vesting_schedule_entry_point = EntryPoint::new(
+        String::from(METHOD_CREATE_VESTING_SCHEDULE),
+        vec![
+            Parameter::new(ARG_BENEFICIARY, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            Parameter::new(ARG_START, u64::cl_type()),
+            Parameter::new(ARG_CLIFF, u64::cl_type()),
+            Parameter::new(ARG_DURATION, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let release_entry_point = EntryPoint::new(
+        String::from(METHOD_RELEASE),
+        Vec::new(),
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let vested_amount_entry_point = EntryPoint::new(
+        String::from(METHOD_VESTED_AMOUNT),
+        vec![Parameter::new(ARG_BENEFICIARY, Address::cl_type())],
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let releasable_amount_entry_point = EntryPoint::new(
+        String::from(METHOD_RELEASABLE_AMOUNT),
+        vec![Parameter::new(ARG_BENEFICIARY, Address::cl_type())],
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let max_flash_loan_entry_point = EntryPoint::new(
+        String::from(METHOD_MAX_FLASH_LOAN),
+        Vec::new(),
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let flash_fee_entry_point = EntryPoint::new(
+        String::from(METHOD_FLASH_FEE),
+        vec![Parameter::new(ARG_AMOUNT, TokenAmount::cl_type())],
+        TokenAmount::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let flash_loan_entry_point = EntryPoint::new(
+        String::from(METHOD_FLASH_LOAN),
+        vec![
+            Parameter::new(ARG_RECEIVER, ContractPackageHash::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            Parameter::new(ARG_DATA, Vec::<u8>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let transfer_and_call_entry_point = EntryPoint::new(
+        String::from(METHOD_TRANSFER_AND_CALL),
+        vec![
+            Parameter::new(ARG_RECIPIENT, ContractPackageHash::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            Parameter::new(ARG_DATA, Vec::<u8>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let approve_and_call_entry_point = EntryPoint::new(
+        String::from(METHOD_APPROVE_AND_CALL),
+        vec![
+            Parameter::new(ARG_SPENDER, ContractPackageHash::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            Parameter::new(ARG_DATA, Vec::<u8>::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     );
 
+    let approve_with_expiry_entry_point = EntryPoint::new(
+        String::from(METHOD_APPROVE_WITH_EXPIRY),
+        vec![
+            Parameter::new(ARG_SPENDER, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            Parameter::new(ARG_EXPIRES_AT, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let increase_allowance_entry_point = EntryPoint::new(
+        String::from(METHOD_INCREASE_ALLOWANCE),
+        vec![
+            Parameter::new(ARG_SPENDER, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let decrease_allowance_entry_point = EntryPoint::new(
+        String::from(METHOD_DECREASE_ALLOWANCE),
+        vec![
+            Parameter::new(ARG_SPENDER, Address::cl_type()),
+            Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let pause_entry_point = EntryPoint::new(
+        String::from(METHOD_PAUSE),
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let unpause_entry_point = EntryPoint::new(
+        String::from(METHOD_UNPAUSE),
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let grant_role_entry_point = EntryPoint::new(
+        String::from(METHOD_GRANT_ROLE),
+        vec![
+            Parameter::new(ARG_ROLE, String::cl_type()),
+            Parameter::new(ARG_ADDRESS, Address::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let revoke_role_entry_point = EntryPoint::new(
+        String::from(METHOD_REVOKE_ROLE),
+        vec![
+            Parameter::new(ARG_ROLE, String::cl_type()),
+            Parameter::new(ARG_ADDRESS, Address::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let renounce_role_entry_point = EntryPoint::new(
+        String::from(METHOD_RENOUNCE_ROLE),
+        vec![
+            Parameter::new(ARG_ROLE, String::cl_type()),
+            Parameter::new(ARG_ADDRESS, Address::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let has_role_entry_point = EntryPoint::new(
+        String::from(METHOD_HAS_ROLE),
+        vec![
+            Parameter::new(ARG_ROLE, String::cl_type()),
+            Parameter::new(ARG_ADDRESS, Address::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let owner_entry_point = EntryPoint::new(
+        String::from(METHOD_OWNER),
+        Vec::new(),
+        Option::<Address>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let transfer_ownership_entry_point = EntryPoint::new(
+        String::from(METHOD_TRANSFER_OWNERSHIP),
+        vec![Parameter::new(ARG_NEW_OWNER, Address::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let accept_ownership_entry_point = EntryPoint::new(
+        String::from(METHOD_ACCEPT_OWNERSHIP),
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
+    let renounce_ownership_entry_point = EntryPoint::new(
+        String::from(METHOD_RENOUNCE_OWNERSHIP),
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+
     entry_points.add_entry_point(name_entry_point);
     entry_points.add_entry_point(symbol_entry_point);
     entry_points.add_entry_point(decimals_entry_point);
     entry_points.add_entry_point(total_supply_entry_point);
+    entry_points.add_entry_point(cap_entry_point);
     entry_points.add_entry_point(balance_of_entry_point);
     entry_points.add_entry_point(transfer_entry_point);
+    entry_points.add_entry_point(batch_transfer_entry_point);
+    entry_points.add_entry_point(transfer_and_call_entry_point);
     entry_points.add_entry_point(approve_entry_point);
+    entry_points.add_entry_point(approve_and_call_entry_point);
+    entry_points.add_entry_point(approve_with_expiry_entry_point);
     entry_points.add_entry_point(allowance_entry_point);
     entry_points.add_entry_point(transfer_from_entry_point);
+    entry_points.add_entry_point(increase_allowance_entry_point);
+    entry_points.add_entry_point(decrease_allowance_entry_point);
+    entry_points.add_entry_point(permit_entry_point);
+    entry_points.add_entry_point(nonces_entry_point);
+    entry_points.add_entry_point(snapshot_entry_point);
+    entry_points.add_entry_point(balance_of_at_entry_point);
+    entry_points.add_entry_point(total_supply_at_entry_point);
+    entry_points.add_entry_point(delegate_entry_point);
+    entry_points.add_entry_point(delegates_entry_point);
+    entry_points.add_entry_point(get_votes_entry_point);
+    entry_points.add_entry_point(get_past_votes_entry_point);
+    entry_points.add_entry_point(pause_entry_point);
+    entry_points.add_entry_point(unpause_entry_point);
+    entry_points.add_entry_point(freeze_entry_point);
+    entry_points.add_entry_point(unfreeze_entry_point);
+    entry_points.add_entry_point(is_frozen_entry_point);
+    entry_points.add_entry_point(set_transfer_fee_entry_point);
+    entry_points.add_entry_point(set_fee_exempt_entry_point);
+    entry_points.add_entry_point(fee_basis_points_entry_point);
+    entry_points.add_entry_point(fee_recipient_entry_point);
+    entry_points.add_entry_point(is_fee_exempt_entry_point);
+    entry_points.add_entry_point(create_vesting_schedule_entry_point);
+    entry_points.add_entry_point(release_entry_point);
+    entry_points.add_entry_point(vested_amount_entry_point);
+    entry_points.add_entry_point(releasable_amount_entry_point);
+    entry_points.add_entry_point(max_flash_loan_entry_point);
+    entry_points.add_entry_point(flash_fee_entry_point);
+    entry_points.add_entry_point(flash_loan_entry_point);
+    entry_points.add_entry_point(grant_role_entry_point);
+    entry_points.add_entry_point(revoke_role_entry_point);
+    entry_points.add_entry_point(renounce_role_entry_point);
+    entry_points.add_entry_point(has_role_entry_point);
+    entry_points.add_entry_point(owner_entry_point);
+    entry_points.add_entry_point(transfer_ownership_entry_point);
+    entry_points.add_entry_point(accept_ownership_entry_point);
+    entry_points.add_entry_point(renounce_ownership_entry_point);
+
+    if mintable {
+        let mint_entry_point = EntryPoint::new(
+            String::from(METHOD_MINT),
+            vec![
+                Parameter::new(ARG_OWNER, Address::cl_type()),
+                Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+
+        let burn_entry_point = EntryPoint::new(
+            String::from(METHOD_BURN),
+            vec![
+                Parameter::new(ARG_OWNER, Address::cl_type()),
+                Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+
+        entry_points.add_entry_point(mint_entry_point);
+        entry_points.add_entry_point(burn_entry_point);
+    }
+
+    if wrapped {
+        let deposit_entry_point = EntryPoint::new(
+            String::from(METHOD_DEPOSIT),
+            vec![
+                Parameter::new(ARG_PURSE, URef::cl_type()),
+                Parameter::new(ARG_AMOUNT, TokenAmount::cl_type()),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+
+        let withdraw_entry_point = EntryPoint::new(
+            String::from(METHOD_WITHDRAW),
+            vec![Parameter::new(ARG_AMOUNT, TokenAmount::cl_type())],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+
+        entry_points.add_entry_point(deposit_entry_point);
+        entry_points.add_entry_point(withdraw_entry_point);
+    }
+
     entry_points
 }
@@ -3,6 +3,7 @@ use casper_types::ApiError;
 
 /// Represents error conditions of the erc20 contract.
 #[repr(u16)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Error {
     /// ERC20 contract called from within invalid context.
     InvalidContext = 0,
@@ -12,6 +13,46 @@ pub enum Error {
     InsufficientAllowance = 2,
     /// Operation would cause an integer overflow.
     Overflow = 3,
+    /// Caller is not authorized to perform the operation.
+    Unauthorized = 4,
+    /// Operation would cause an integer underflow.
+    Underflow = 5,
+    /// Token is paused.
+    Paused = 6,
+    /// Operation would increase total supply above the cap.
+    CapExceeded = 7,
+    /// Permit deadline has passed.
+    PermitExpired = 8,
+    /// Permit nonce does not match the current nonce of the owner.
+    InvalidNonce = 9,
+    /// Signature is malformed or was not made by the expected key.
+    InvalidSignature = 10,
+    /// Snapshot with a given id was not taken yet.
+    InvalidSnapshot = 11,
+    /// Lookup of a past value was made for a time that is not in the past yet.
+    FutureLookup = 12,
+    /// Address involved in the operation is frozen.
+    AccountFrozen = 13,
+    /// Fee exceeds the whole transferred amount.
+    InvalidFee = 14,
+    /// Beneficiary already has a vesting schedule.
+    VestingScheduleExists = 15,
+    /// Vesting schedule has a zero duration, or a cliff longer than its duration.
+    InvalidVestingSchedule = 16,
+    /// Beneficiary has no vesting schedule.
+    NoVestingSchedule = 17,
+    /// Lists passed together have different lengths.
+    LengthMismatch = 18,
+    /// Flash loan exceeds the maximum amount available.
+    FlashLoanTooLarge = 19,
+    /// Receiver of a flash loan rejected it.
+    FlashLoanRejected = 20,
+    /// Receiver contract rejected tokens transferred or approved to it.
+    ReceiverRejected = 21,
+    /// Wrapped token has an initial supply, is mintable, or has decimals other than CSPR.
+    InvalidWrappedToken = 22,
+    /// Caller already installed a token with the same name.
+    TokenAlreadyInstalled = 23,
 }
 
 impl From<Error> for ApiError {
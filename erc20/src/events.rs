@@ -0,0 +1,203 @@
+//! Implementation of events.
+//!
+//! Events are appended to a dictionary under a sequential index, and the number of events emitted so far is stored under a separate named key.
+use alloc::{string::ToString, vec::Vec};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, URef,
+};
+
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    constants::{EVENTS_KEY, EVENT_COUNT_KEY},
+    host::{Host, UnwrapOrRevert as _},
+};
+
+const TRANSFER_TAG: u8 = 0;
+const APPROVAL_TAG: u8 = 1;
+const SNAPSHOT_TAG: u8 = 2;
+const DELEGATE_CHANGED_TAG: u8 = 3;
+
+/// Represents an event emitted by the ERC20 contract.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Event {
+    /// Emitted when `amount` of tokens is moved from one address to another.
+    ///
+    /// `from` is `None` when tokens are minted, and `to` is `None` when tokens are burned.
+    Transfer {
+        /// Address tokens are moved from.
+        from: Option<Address>,
+        /// Address tokens are moved to.
+        to: Option<Address>,
+        /// Amount of tokens moved.
+        amount: TokenAmount,
+    },
+    /// Emitted when an allowance of a `spender` for an `owner` is set.
+    Approval {
+        /// Owner of the tokens.
+        owner: Address,
+        /// Address allowed to spend the tokens.
+        spender: Address,
+        /// New allowance.
+        amount: TokenAmount,
+    },
+    /// Emitted when a snapshot is taken.
+    Snapshot {
+        /// Id of the snapshot.
+        id: u64,
+    },
+    /// Emitted when a `delegator` changes its delegatee.
+    ///
+    /// `from_delegate` is `None` when the `delegator` delegates for the first time.
+    DelegateChanged {
+        /// Address that delegates its voting power.
+        delegator: Address,
+        /// Previous delegatee.
+        from_delegate: Option<Address>,
+        /// New delegatee.
+        to_delegate: Address,
+    },
+}
+
+impl CLTyped for Event {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Event {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(self.serialized_length());
+        match self {
+            Event::Transfer { from, to, amount } => {
+                result.push(TRANSFER_TAG);
+                result.append(&mut from.to_bytes()?);
+                result.append(&mut to.to_bytes()?);
+                result.append(&mut amount.to_bytes()?);
+            }
+            Event::Approval {
+                owner,
+                spender,
+                amount,
+            } => {
+                result.push(APPROVAL_TAG);
+                result.append(&mut owner.to_bytes()?);
+                result.append(&mut spender.to_bytes()?);
+                result.append(&mut amount.to_bytes()?);
+            }
+            Event::Snapshot { id } => {
+                result.push(SNAPSHOT_TAG);
+                result.append(&mut id.to_bytes()?);
+            }
+            Event::DelegateChanged {
+                delegator,
+                from_delegate,
+                to_delegate,
+            } => {
+                result.push(DELEGATE_CHANGED_TAG);
+                result.append(&mut delegator.to_bytes()?);
+                result.append(&mut from_delegate.to_bytes()?);
+                result.append(&mut to_delegate.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                Event::Transfer { from, to, amount } => {
+                    from.serialized_length() + to.serialized_length() + amount.serialized_length()
+                }
+                Event::Approval {
+                    owner,
+                    spender,
+                    amount,
+                } => {
+                    owner.serialized_length()
+                        + spender.serialized_length()
+                        + amount.serialized_length()
+                }
+                Event::Snapshot { id } => id.serialized_length(),
+                Event::DelegateChanged {
+                    delegator,
+                    from_delegate,
+                    to_delegate,
+                } => {
+                    delegator.serialized_length()
+                        + from_delegate.serialized_length()
+                        + to_delegate.serialized_length()
+                }
+            }
+    }
+}
+
+impl FromBytes for Event {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            TRANSFER_TAG => {
+                let (from, remainder) = Option::<Address>::from_bytes(remainder)?;
+                let (to, remainder) = Option::<Address>::from_bytes(remainder)?;
+                let (amount, remainder) = TokenAmount::from_bytes(remainder)?;
+                Ok((Event::Transfer { from, to, amount }, remainder))
+            }
+            APPROVAL_TAG => {
+                let (owner, remainder) = Address::from_bytes(remainder)?;
+                let (spender, remainder) = Address::from_bytes(remainder)?;
+                let (amount, remainder) = TokenAmount::from_bytes(remainder)?;
+                let event = Event::Approval {
+                    owner,
+                    spender,
+                    amount,
+                };
+                Ok((event, remainder))
+            }
+            SNAPSHOT_TAG => {
+                let (id, remainder) = u64::from_bytes(remainder)?;
+                Ok((Event::Snapshot { id }, remainder))
+            }
+            DELEGATE_CHANGED_TAG => {
+                let (delegator, remainder) = Address::from_bytes(remainder)?;
+                let (from_delegate, remainder) = Option::<Address>::from_bytes(remainder)?;
+                let (to_delegate, remainder) = Address::from_bytes(remainder)?;
+                let event = Event::DelegateChanged {
+                    delegator,
+                    from_delegate,
+                    to_delegate,
+                };
+                Ok((event, remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[inline]
+fn get_events_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(EVENTS_KEY)
+}
+
+#[inline]
+fn get_event_count_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(EVENT_COUNT_KEY)
+}
+
+/// Emits an event.
+pub fn emit<H: Host>(host: &H, event: Event) {
+    emit_into(
+        host,
+        get_events_uref(host),
+        get_event_count_uref(host),
+        event,
+    );
+}
+
+/// Emits an event into a specified events dictionary, and bumps the event counter.
+pub fn emit_into<H: Host>(host: &H, events_uref: URef, event_count_uref: URef, event: Event) {
+    let event_count: u64 = host.read(event_count_uref).unwrap_or_revert_on(host);
+    host.dictionary_put(events_uref, &event_count.to_string(), event);
+    host.write(event_count_uref, event_count + 1);
+}
@@ -0,0 +1,121 @@
+//! Implementation of a transfer fee.
+//!
+//! When a fee policy is set, every transfer between two addresses that are not exempt pays a fee of `fee_basis_points / 10_000` of the transferred amount to the fee recipient, and the recipient of the transfer receives the rest. The fee is rounded down, so a transfer of a tiny amount may pay no fee at all.
+use casper_types::URef;
+
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    constants::{FEE_BASIS_POINTS_KEY, FEE_EXEMPTIONS_KEY, FEE_RECIPIENT_KEY},
+    detail,
+    error::Error,
+    host::{Host, UnwrapOrRevert as _},
+};
+
+/// Number of basis points that make up the whole transferred amount.
+pub const MAX_FEE_BASIS_POINTS: u32 = 10_000;
+
+#[inline]
+fn get_fee_basis_points_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(FEE_BASIS_POINTS_KEY)
+}
+
+#[inline]
+fn get_fee_recipient_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(FEE_RECIPIENT_KEY)
+}
+
+#[inline]
+fn get_fee_exemptions_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(FEE_EXEMPTIONS_KEY)
+}
+
+/// Reads the fee in basis points.
+pub fn read_fee_basis_points<H: Host>(host: &H) -> u32 {
+    let fee_basis_points_uref = get_fee_basis_points_uref(host);
+    host.read(fee_basis_points_uref).unwrap_or_revert_on(host)
+}
+
+/// Reads the recipient of the fee, or `None` if no fee policy was set.
+pub fn read_fee_recipient<H: Host>(host: &H) -> Option<Address> {
+    let fee_recipient_uref = get_fee_recipient_uref(host);
+    host.read(fee_recipient_uref).unwrap_or_revert_on(host)
+}
+
+/// Writes the fee policy.
+///
+/// Fails with [`Error::InvalidFee`] if `fee_basis_points` exceed [`MAX_FEE_BASIS_POINTS`].
+pub fn write_fee_policy<H: Host>(
+    host: &H,
+    fee_basis_points: u32,
+    fee_recipient: &Address,
+) -> Result<(), Error> {
+    if fee_basis_points > MAX_FEE_BASIS_POINTS {
+        return Err(Error::InvalidFee);
+    }
+
+    host.write(get_fee_basis_points_uref(host), fee_basis_points);
+    host.write(get_fee_recipient_uref(host), Some(*fee_recipient));
+
+    Ok(())
+}
+
+/// Checks if an address is exempt from the fee.
+pub fn is_fee_exempt<H: Host>(host: &H, address: &Address) -> bool {
+    let fee_exemptions_uref = get_fee_exemptions_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, address);
+
+    host.dictionary_get(fee_exemptions_uref, &dictionary_item_key)
+        .unwrap_or_default()
+}
+
+/// Writes a fee exemption flag of an address.
+pub fn write_fee_exempt<H: Host>(host: &H, address: &Address, exempt: bool) {
+    let fee_exemptions_uref = get_fee_exemptions_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, address);
+    host.dictionary_put(fee_exemptions_uref, &dictionary_item_key, exempt);
+}
+
+/// Computes `amount * fee_basis_points / MAX_FEE_BASIS_POINTS` rounded down.
+///
+/// The amount is split into whole multiples of [`MAX_FEE_BASIS_POINTS`] and the remainder, so the intermediate product never exceeds the `amount`.
+fn fee_of(amount: TokenAmount, fee_basis_points: u32) -> Result<TokenAmount, Error> {
+    let max_fee_basis_points = TokenAmount::from(MAX_FEE_BASIS_POINTS);
+    let fee_basis_points = TokenAmount::from(fee_basis_points);
+
+    let whole = (amount / max_fee_basis_points)
+        .checked_mul(fee_basis_points)
+        .ok_or(Error::Overflow)?;
+    let remainder = (amount % max_fee_basis_points)
+        .checked_mul(fee_basis_points)
+        .ok_or(Error::Overflow)?
+        / max_fee_basis_points;
+
+    whole.checked_add(remainder).ok_or(Error::Overflow)
+}
+
+/// Calculates the fee of a transfer, and returns it along with its recipient.
+///
+/// Returns `None` if no fee policy is set, either side of the transfer is exempt, or the fee rounds down to zero.
+pub fn calculate_fee<H: Host>(
+    host: &H,
+    sender: &Address,
+    recipient: &Address,
+    amount: TokenAmount,
+) -> Result<Option<(Address, TokenAmount)>, Error> {
+    let fee_recipient = match read_fee_recipient(host) {
+        Some(fee_recipient) => fee_recipient,
+        None => return Ok(None),
+    };
+
+    if is_fee_exempt(host, sender) || is_fee_exempt(host, recipient) {
+        return Ok(None);
+    }
+
+    let fee = fee_of(amount, read_fee_basis_points(host))?;
+    if fee.is_zero() {
+        Ok(None)
+    } else {
+        Ok(Some((fee_recipient, fee)))
+    }
+}
@@ -0,0 +1,69 @@
+//! Implementation of flash mints following ERC-3156.
+//!
+//! Tokens are minted to a receiver contract, its `on_flash_loan` entry point is called, and then the borrowed amount plus a fee is burned back from the receiver within the same execution. A receiver that doesn't hold enough tokens by then fails the whole flash loan.
+use alloc::vec::Vec;
+
+use casper_types::{runtime_args, ContractPackageHash, RuntimeArgs};
+
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    constants::{ARG_AMOUNT, ARG_DATA, ARG_FEE, ARG_INITIATOR, CAP_KEY, METHOD_ON_FLASH_LOAN},
+    detail,
+    error::Error,
+    host::Host,
+    internal, total_supply,
+};
+
+/// Returns the maximum amount of tokens available for a flash loan.
+///
+/// It's the amount that can be minted without exceeding the cap, or overflowing the total supply if there is no cap.
+pub fn max_flash_loan<H: Host>(host: &H) -> TokenAmount {
+    let cap: Option<TokenAmount> = detail::read_from(host, CAP_KEY);
+    let total_supply = total_supply::read_total_supply(host);
+
+    cap.unwrap_or_else(TokenAmount::max_value)
+        .saturating_sub(total_supply)
+}
+
+/// Returns the fee charged for a flash loan of `amount`.
+///
+/// Flash loans are free, but the fee is still passed to the receiver and burned on repayment, so a custom contract can charge one by changing this function.
+pub fn flash_fee(_amount: TokenAmount) -> TokenAmount {
+    TokenAmount::zero()
+}
+
+/// Lends `amount` of freshly minted tokens to a `receiver` contract on behalf of an `initiator`.
+///
+/// The receiver's `on_flash_loan` entry point has to return `true`, or the loan fails with [`Error::FlashLoanRejected`]. It fails with [`Error::FlashLoanTooLarge`] if the `amount` exceeds [`max_flash_loan`], and with [`Error::InsufficientBalance`] if the receiver can't repay the `amount` plus the fee.
+pub fn flash_loan<H: Host>(
+    host: &H,
+    initiator: &Address,
+    receiver: ContractPackageHash,
+    amount: TokenAmount,
+    data: Vec<u8>,
+) -> Result<(), Error> {
+    if amount > max_flash_loan(host) {
+        return Err(Error::FlashLoanTooLarge);
+    }
+
+    let fee = flash_fee(amount);
+    let receiver_address = Address::from(receiver);
+
+    internal::mint(host, &receiver_address, amount)?;
+
+    let on_flash_loan_args = runtime_args! {
+        ARG_INITIATOR => *initiator,
+        ARG_AMOUNT => amount,
+        ARG_FEE => fee,
+        ARG_DATA => data,
+    };
+    let accepted: bool =
+        host.call_versioned_contract(receiver, METHOD_ON_FLASH_LOAN, on_flash_loan_args);
+    if !accepted {
+        return Err(Error::FlashLoanRejected);
+    }
+
+    let repayment = amount.checked_add(fee).ok_or(Error::Overflow)?;
+    internal::burn(host, &receiver_address, repayment)
+}
@@ -0,0 +1,37 @@
+//! Implementation of frozen accounts.
+//!
+//! A frozen address can neither send nor receive tokens until it is unfrozen. Token movements involving a frozen address fail with [`Error::AccountFrozen`].
+use casper_types::URef;
+
+use crate::{address::Address, constants::FROZEN_ACCOUNTS_KEY, detail, error::Error, host::Host};
+
+#[inline]
+fn get_frozen_accounts_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(FROZEN_ACCOUNTS_KEY)
+}
+
+/// Checks if an address is frozen.
+pub fn is_frozen<H: Host>(host: &H, address: &Address) -> bool {
+    let frozen_accounts_uref = get_frozen_accounts_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, address);
+
+    host.dictionary_get(frozen_accounts_uref, &dictionary_item_key)
+        .unwrap_or_default()
+}
+
+/// Writes a frozen flag of an address.
+pub fn write_frozen<H: Host>(host: &H, address: &Address, frozen: bool) {
+    let frozen_accounts_uref = get_frozen_accounts_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, address);
+    host.dictionary_put(frozen_accounts_uref, &dictionary_item_key, frozen);
+}
+
+/// Makes sure that an address is not frozen.
+#[inline]
+pub fn requires_not_frozen<H: Host>(host: &H, address: &Address) -> Result<(), Error> {
+    if is_frozen(host, address) {
+        Err(Error::AccountFrozen)
+    } else {
+        Ok(())
+    }
+}
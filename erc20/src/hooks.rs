@@ -0,0 +1,73 @@
+//! Implementation of receiver hooks following ERC-1363.
+//!
+//! After tokens are transferred to, or approved for a contract, its hook entry point is called so it can react within the same deploy. A hook accepts by returning `true`, and anything else fails the whole operation.
+use alloc::vec::Vec;
+
+use casper_types::{runtime_args, ContractPackageHash, RuntimeArgs};
+
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    constants::{
+        ARG_AMOUNT, ARG_DATA, ARG_FROM, ARG_OPERATOR, ARG_OWNER, METHOD_ON_APPROVAL_RECEIVED,
+        METHOD_ON_TRANSFER_RECEIVED,
+    },
+    error::Error,
+    host::Host,
+};
+
+/// Calls the `on_transfer_received` entry point of a `recipient` contract.
+///
+/// The `operator` is the address that made the transfer on behalf of the `from` address, and the `amount` is what the `recipient` received after any transfer fee.
+pub fn call_on_transfer_received<H: Host>(
+    host: &H,
+    recipient: ContractPackageHash,
+    operator: &Address,
+    from: &Address,
+    amount: TokenAmount,
+    data: Vec<u8>,
+) -> Result<(), Error> {
+    let on_transfer_received_args = runtime_args! {
+        ARG_OPERATOR => *operator,
+        ARG_FROM => *from,
+        ARG_AMOUNT => amount,
+        ARG_DATA => data,
+    };
+    let accepted: bool = host.call_versioned_contract(
+        recipient,
+        METHOD_ON_TRANSFER_RECEIVED,
+        on_transfer_received_args,
+    );
+
+    if accepted {
+        Ok(())
+    } else {
+        Err(Error::ReceiverRejected)
+    }
+}
+
+/// Calls the `on_approval_received` entry point of a `spender` contract.
+pub fn call_on_approval_received<H: Host>(
+    host: &H,
+    spender: ContractPackageHash,
+    owner: &Address,
+    amount: TokenAmount,
+    data: Vec<u8>,
+) -> Result<(), Error> {
+    let on_approval_received_args = runtime_args! {
+        ARG_OWNER => *owner,
+        ARG_AMOUNT => amount,
+        ARG_DATA => data,
+    };
+    let accepted: bool = host.call_versioned_contract(
+        spender,
+        METHOD_ON_APPROVAL_RECEIVED,
+        on_approval_received_args,
+    );
+
+    if accepted {
+        Ok(())
+    } else {
+        Err(Error::ReceiverRejected)
+    }
+}
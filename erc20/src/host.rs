@@ -0,0 +1,242 @@
+//! Implementation of a host the token is running on.
+//!
+//! The library accesses contract storage, the caller, the block time, other contracts and purses only through the [`Host`] trait, so its logic can run both on the Casper host through [`CasperHost`], and natively against an in-memory host in unit tests.
+#[cfg(feature = "casper")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "casper")]
+use core::convert::TryInto;
+
+#[cfg(feature = "casper")]
+use casper_contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert as _,
+};
+#[cfg(feature = "casper")]
+use casper_types::system::CallStackElement;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, CLTyped, ContractPackageHash, RuntimeArgs, URef, U512,
+};
+
+use crate::{address::Address, error::Error};
+
+/// Storage and runtime functions used by the library.
+pub trait Host {
+    /// Gets [`URef`] under a name.
+    ///
+    /// Storage modules look it up on every access, so a host running on a node should cache it.
+    fn get_uref(&self, name: &str) -> URef;
+
+    /// Reads value stored under a [`URef`], or `None` if nothing was stored there.
+    fn read<T: CLTyped + FromBytes>(&self, uref: URef) -> Option<T>;
+
+    /// Writes value under a [`URef`].
+    fn write<T: CLTyped + ToBytes>(&self, uref: URef, value: T);
+
+    /// Reads value stored under a dictionary item key, or `None` if nothing was stored there.
+    fn dictionary_get<T: CLTyped + FromBytes>(
+        &self,
+        dictionary_uref: URef,
+        dictionary_item_key: &str,
+    ) -> Option<T>;
+
+    /// Writes value under a dictionary item key.
+    fn dictionary_put<T: CLTyped + ToBytes>(
+        &self,
+        dictionary_uref: URef,
+        dictionary_item_key: &str,
+        value: T,
+    );
+
+    /// Computes a BLAKE2b hash of the input.
+    fn blake2b(&self, input: &[u8]) -> [u8; 32];
+
+    /// Gets the immediate caller of the current execution.
+    ///
+    /// Session code is identified by its account hash, and a stored contract is identified by its contract package hash. Stored session code is disallowed.
+    fn get_immediate_caller(&self) -> Result<Address, Error>;
+
+    /// Gets the contract package hash of the currently executing contract.
+    ///
+    /// An attempt to call this function from outside of a stored contract will fail with [`Error::InvalidContext`].
+    fn get_current_contract_package_hash(&self) -> Result<ContractPackageHash, Error>;
+
+    /// Gets the current block time in milliseconds since the Unix epoch.
+    fn get_blocktime(&self) -> u64;
+
+    /// Calls an entry point of the latest version of a contract package, and returns its result.
+    fn call_versioned_contract<T: CLTyped + FromBytes>(
+        &self,
+        contract_package_hash: ContractPackageHash,
+        entry_point_name: &str,
+        runtime_args: RuntimeArgs,
+    ) -> T;
+
+    /// Transfers `amount` of motes from a `source` purse to a `target` purse.
+    fn transfer_from_purse_to_purse(
+        &self,
+        source: URef,
+        target: URef,
+        amount: U512,
+    ) -> Result<(), ApiError>;
+
+    /// Transfers `amount` of motes from a `source` purse to the main purse of a `target` account.
+    fn transfer_from_purse_to_account(
+        &self,
+        source: URef,
+        target: AccountHash,
+        amount: U512,
+    ) -> Result<(), ApiError>;
+
+    /// Stops the execution and reverts all changes with an error.
+    fn revert<E: Into<ApiError>>(&self, error: E) -> !;
+}
+
+/// Unwraps a value, or reverts through a [`Host`] otherwise.
+pub trait UnwrapOrRevert<T> {
+    /// Unwraps the value, or reverts with an error that describes the failure.
+    fn unwrap_or_revert_on<H: Host>(self, host: &H) -> T;
+}
+
+impl<T> UnwrapOrRevert<T> for Option<T> {
+    fn unwrap_or_revert_on<H: Host>(self, host: &H) -> T {
+        self.unwrap_or_else(|| host.revert(ApiError::None))
+    }
+}
+
+impl<T, E: Into<ApiError>> UnwrapOrRevert<T> for Result<T, E> {
+    fn unwrap_or_revert_on<H: Host>(self, host: &H) -> T {
+        self.unwrap_or_else(|error| host.revert(error))
+    }
+}
+
+/// Host backed by the host functions of a Casper node.
+#[cfg(feature = "casper")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CasperHost;
+
+#[cfg(feature = "casper")]
+impl CasperHost {
+    /// Gets the immediate call stack element of the current execution.
+    fn get_immediate_call_stack_item(&self) -> Option<CallStackElement> {
+        let call_stack = runtime::get_call_stack();
+        call_stack.into_iter().rev().nth(1)
+    }
+}
+
+/// URefs already looked up by [`CasperHost`], so every named key is read at most once per execution.
+///
+/// Contract code runs single-threaded on a Casper node, so the cache is never accessed concurrently.
+#[cfg(feature = "casper")]
+static mut UREF_CACHE: Vec<(String, URef)> = Vec::new();
+
+#[cfg(feature = "casper")]
+impl Host for CasperHost {
+    fn get_uref(&self, name: &str) -> URef {
+        unsafe {
+            if let Some((_, uref)) = UREF_CACHE.iter().find(|(cached, _)| cached == name) {
+                return *uref;
+            }
+
+            let key = runtime::get_key(name).unwrap_or_revert();
+            let uref = key.try_into().unwrap_or_revert();
+            UREF_CACHE.push((name.into(), uref));
+            uref
+        }
+    }
+
+    fn read<T: CLTyped + FromBytes>(&self, uref: URef) -> Option<T> {
+        storage::read(uref).unwrap_or_revert()
+    }
+
+    fn write<T: CLTyped + ToBytes>(&self, uref: URef, value: T) {
+        storage::write(uref, value)
+    }
+
+    fn dictionary_get<T: CLTyped + FromBytes>(
+        &self,
+        dictionary_uref: URef,
+        dictionary_item_key: &str,
+    ) -> Option<T> {
+        storage::dictionary_get(dictionary_uref, dictionary_item_key).unwrap_or_revert()
+    }
+
+    fn dictionary_put<T: CLTyped + ToBytes>(
+        &self,
+        dictionary_uref: URef,
+        dictionary_item_key: &str,
+        value: T,
+    ) {
+        storage::dictionary_put(dictionary_uref, dictionary_item_key, value)
+    }
+
+    fn blake2b(&self, input: &[u8]) -> [u8; 32] {
+        runtime::blake2b(input)
+    }
+
+    #[inline]
+    fn get_immediate_caller(&self) -> Result<Address, Error> {
+        match self.get_immediate_call_stack_item() {
+            Some(CallStackElement::Session { account_hash }) => Ok(account_hash.into()),
+            Some(CallStackElement::StoredContract {
+                contract_package_hash,
+                ..
+            }) => Ok(contract_package_hash.into()),
+            Some(CallStackElement::StoredSession { .. }) | None => Err(Error::InvalidContext),
+        }
+    }
+
+    fn get_current_contract_package_hash(&self) -> Result<ContractPackageHash, Error> {
+        let call_stack = runtime::get_call_stack();
+
+        match call_stack.into_iter().rev().next() {
+            Some(CallStackElement::StoredContract {
+                contract_package_hash,
+                ..
+            }) => Ok(contract_package_hash),
+            _ => Err(Error::InvalidContext),
+        }
+    }
+
+    #[inline]
+    fn get_blocktime(&self) -> u64 {
+        u64::from(runtime::get_blocktime())
+    }
+
+    fn call_versioned_contract<T: CLTyped + FromBytes>(
+        &self,
+        contract_package_hash: ContractPackageHash,
+        entry_point_name: &str,
+        runtime_args: RuntimeArgs,
+    ) -> T {
+        runtime::call_versioned_contract(
+            contract_package_hash,
+            None,
+            entry_point_name,
+            runtime_args,
+        )
+    }
+
+    fn transfer_from_purse_to_purse(
+        &self,
+        source: URef,
+        target: URef,
+        amount: U512,
+    ) -> Result<(), ApiError> {
+        system::transfer_from_purse_to_purse(source, target, amount, None)
+    }
+
+    fn transfer_from_purse_to_account(
+        &self,
+        source: URef,
+        target: AccountHash,
+        amount: U512,
+    ) -> Result<(), ApiError> {
+        system::transfer_from_purse_to_account(source, target, amount, None).map(|_| ())
+    }
+
+    fn revert<E: Into<ApiError>>(&self, error: E) -> ! {
+        runtime::revert(error)
+    }
+}
@@ -4,36 +4,108 @@
 //!
 //! Those functions should never be called from entrypoints marked as public.
 
-use casper_types::{account::AccountHash, U512};
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    balances,
+    constants::CAP_KEY,
+    detail,
+    error::Error,
+    events::{self, Event},
+    frozen,
+    host::Host,
+    pausable, total_supply, votes,
+};
 
-use crate::{balances, error::Error};
-
-/// Internal function that mints an amount of the token and assigns it to an account.
+/// Internal function that mints an amount of the token and assigns it to an address.
+///
+/// Total supply is increased by the minted amount. It fails with [`Error::Paused`] if the token is paused, with [`Error::AccountFrozen`] if the `owner` is frozen, and with [`Error::CapExceeded`] if the total supply would exceed the cap.
 ///
 /// # Security
 ///
 /// This offers no security whatsoever, and for all practical purposes user of this function is advised to NOT expose this function through a public entry point.
-pub fn mint(owner: &AccountHash, amount: U512) -> Result<(), Error> {
+pub fn mint<H: Host>(host: &H, owner: &Address, amount: TokenAmount) -> Result<(), Error> {
+    pausable::requires_not_paused(host)?;
+    frozen::requires_not_frozen(host, owner)?;
+
+    let new_total_supply = {
+        let total_supply = total_supply::read_total_supply(host);
+        total_supply.checked_add(amount).ok_or(Error::Overflow)?
+    };
+    let cap: Option<TokenAmount> = detail::read_from(host, CAP_KEY);
+    if matches!(cap, Some(cap) if new_total_supply > cap) {
+        return Err(Error::CapExceeded);
+    }
     let new_balance = {
-        let balance = balances::read_balance(owner);
+        let balance = balances::read_balance(host, owner);
         balance.checked_add(amount).ok_or(Error::Overflow)?
     };
-    balances::write_balance(owner, new_balance);
+    balances::write_balance(host, owner, new_balance);
+    total_supply::write_total_supply(host, new_total_supply);
+    votes::move_voting_power(host, None, votes::read_delegate(host, owner), amount)?;
+    events::emit(
+        host,
+        Event::Transfer {
+            from: None,
+            to: Some(*owner),
+            amount,
+        },
+    );
     Ok(())
 }
 
-/// Internal function that burns an amount of the token of a given account.
+/// Internal function that burns an amount of the token of a given address.
+///
+/// Total supply is decreased by the burned amount. It fails with [`Error::Paused`] if the token is paused.
 ///
 /// # Security
 ///
 /// This offers no security whatsoever, and for all practical purposes user of this function is advised to NOT expose this function through a public entry point.
-pub fn burn(owner: &AccountHash, amount: U512) -> Result<(), Error> {
+pub fn burn<H: Host>(host: &H, owner: &Address, amount: TokenAmount) -> Result<(), Error> {
+    pausable::requires_not_paused(host)?;
+
     let new_balance = {
-        let balance = balances::read_balance(owner);
+        let balance = balances::read_balance(host, owner);
         balance
             .checked_sub(amount)
             .ok_or(Error::InsufficientBalance)?
     };
-    balances::write_balance(owner, new_balance);
+    let new_total_supply = {
+        let total_supply = total_supply::read_total_supply(host);
+        total_supply.checked_sub(amount).ok_or(Error::Overflow)?
+    };
+    balances::write_balance(host, owner, new_balance);
+    total_supply::write_total_supply(host, new_total_supply);
+    votes::move_voting_power(host, votes::read_delegate(host, owner), None, amount)?;
+    events::emit(
+        host,
+        Event::Transfer {
+            from: Some(*owner),
+            to: None,
+            amount,
+        },
+    );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use casper_types::account::AccountHash;
+
+    use super::*;
+    use crate::mock::MockHost;
+
+    const ALICE: Address = Address::Account(AccountHash::new([1; 32]));
+
+    #[test]
+    fn should_not_mint_to_frozen_address() {
+        let host = MockHost::new();
+        frozen::write_frozen(&host, &ALICE, true);
+
+        assert_eq!(
+            mint(&host, &ALICE, TokenAmount::one()),
+            Err(Error::AccountFrozen)
+        );
+        assert_eq!(total_supply::read_total_supply(&host), TokenAmount::zero());
+    }
+}
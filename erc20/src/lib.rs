@@ -4,89 +4,687 @@
 
 extern crate alloc;
 
+pub mod access_control;
+pub mod address;
 pub mod allowances;
+pub mod amount;
 pub mod balances;
 pub mod constants;
 pub mod detail;
 pub mod entry_points;
 pub mod error;
+pub mod events;
+pub mod fee;
+pub mod flash_mint;
+pub mod frozen;
+pub mod hooks;
+pub mod host;
 pub mod internal;
+#[cfg(any(test, feature = "test-support"))]
+pub mod mock;
+pub mod ownable;
+pub mod pausable;
+pub mod permit;
+pub mod snapshots;
+pub mod total_supply;
+pub mod vesting;
+pub mod votes;
+pub mod wrapped;
 
-use alloc::string::{String, ToString};
+use alloc::{string::String, vec::Vec};
 
+#[cfg(feature = "casper")]
+use alloc::string::ToString;
+#[cfg(feature = "casper")]
 use casper_contract::{
-    contract_api::{runtime, storage},
+    contract_api::{runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use casper_types::{account::AccountHash, contracts::NamedKeys, Key, U512};
+#[cfg(feature = "casper")]
+use casper_types::{contracts::NamedKeys, ContractHash, Key};
+use casper_types::{ContractPackageHash, PublicKey, URef};
 
+use access_control::{ADMIN_ROLE, MINTER_ROLE, PAUSER_ROLE};
+use address::Address;
+use amount::TokenAmount;
+#[cfg(feature = "casper")]
 use constants::{
-    ALLOWANCES_KEY, BALANCES_KEY, CONTRACT_KEY, DECIMALS_KEY, NAME_KEY, SYMBOL_KEY,
-    TOTAL_SUPPLY_KEY,
+    ACCOUNT_SNAPSHOTS_KEY, ALLOWANCES_KEY, BALANCES_KEY, CONTRACT_ACCESS_KEY, CONTRACT_KEY,
+    CONTRACT_PACKAGE_KEY, CONTRACT_WRAPPED_KEY, DELEGATES_KEY, EVENTS_KEY, EVENT_COUNT_KEY,
+    FEE_BASIS_POINTS_KEY, FEE_EXEMPTIONS_KEY, FEE_RECIPIENT_KEY, FROZEN_ACCOUNTS_KEY, NONCES_KEY,
+    OWNER_KEY, PAUSED_KEY, PENDING_OWNER_KEY, PURSE_KEY, ROLES_KEY, ROLE_ADMINS_KEY,
+    SNAPSHOT_ID_KEY, TOTAL_SUPPLY_KEY, TOTAL_SUPPLY_SNAPSHOTS_KEY, VESTING_SCHEDULES_KEY,
+    VOTE_CHECKPOINTS_KEY,
 };
+use constants::{CAP_KEY, DECIMALS_KEY, NAME_KEY, SYMBOL_KEY};
 use error::Error;
+use events::Event;
+#[cfg(feature = "casper")]
+use host::CasperHost;
+use host::Host;
 
 /// Returns name of the token.
-pub fn name() -> String {
-    detail::read_from(NAME_KEY)
+pub fn name<H: Host>(host: &H) -> String {
+    detail::read_from(host, NAME_KEY)
 }
 
 /// Returns symbol of the token.
-pub fn symbol() -> String {
-    detail::read_from(SYMBOL_KEY)
+pub fn symbol<H: Host>(host: &H) -> String {
+    detail::read_from(host, SYMBOL_KEY)
 }
 
 /// Returns decimals of the token.
-pub fn decimals() -> u8 {
-    detail::read_from(DECIMALS_KEY)
+pub fn decimals<H: Host>(host: &H) -> u8 {
+    detail::read_from(host, DECIMALS_KEY)
+}
+
+/// Returns total supply of the token.
+pub fn total_supply<H: Host>(host: &H) -> TokenAmount {
+    total_supply::read_total_supply(host)
+}
+
+/// Returns the maximum total supply of the token, or `None` if the supply is not capped.
+pub fn cap<H: Host>(host: &H) -> Option<TokenAmount> {
+    detail::read_from(host, CAP_KEY)
 }
 
 /// Checks balance of an owner.
-pub fn balance_of(owner: AccountHash) -> U512 {
-    balances::read_balance(&owner)
+pub fn balance_of<H: Host>(host: &H, owner: Address) -> TokenAmount {
+    balances::read_balance(host, &owner)
 }
 
 /// Transfer tokens from the caller to the `recipient`.
-pub fn transfer(recipient: &AccountHash, amount: U512) -> Result<(), Error> {
-    let sender = detail::get_immediate_caller()?;
+pub fn transfer<H: Host>(host: &H, recipient: &Address, amount: TokenAmount) -> Result<(), Error> {
+    let sender = host.get_immediate_caller()?;
+
+    balances::transfer_balance(host, &sender, recipient, amount)?;
+
+    Ok(())
+}
+
+/// Transfer tokens from the caller to a `recipient` contract, and calls its `on_transfer_received` entry point.
+///
+/// The recipient is identified by its contract package hash, and the latest version of it is called with the caller as both the `operator` and `from`, the amount it received and the `data`. The amount received is less than `amount` when a transfer fee applies. The transfer is reverted unless the hook returns `true`.
+pub fn transfer_and_call<H: Host>(
+    host: &H,
+    recipient: ContractPackageHash,
+    amount: TokenAmount,
+    data: Vec<u8>,
+) -> Result<(), Error> {
+    let sender = host.get_immediate_caller()?;
+
+    let received_amount =
+        balances::transfer_balance(host, &sender, &Address::from(recipient), amount)?;
 
-    balances::transfer_balance(&sender, recipient, amount)
+    hooks::call_on_transfer_received(host, recipient, &sender, &sender, received_amount, data)
+}
+
+/// Transfer tokens from the caller to many `recipients` at once.
+///
+/// Each recipient receives the amount at the same position in `amounts`. All transfers are checked before any balance is written, so either all of them succeed or none of them is made.
+pub fn batch_transfer<H: Host>(
+    host: &H,
+    recipients: Vec<Address>,
+    amounts: Vec<TokenAmount>,
+) -> Result<(), Error> {
+    let sender = host.get_immediate_caller()?;
+
+    balances::batch_transfer_balance(host, &sender, &recipients, &amounts)
 }
 
 /// Allow other address to transfer caller's tokens.
-pub fn approve(spender: AccountHash, amount: U512) -> Result<(), Error> {
-    let owner = detail::get_immediate_caller()?;
+///
+/// Approving [`allowances::UNLIMITED_ALLOWANCE`] allows the spender to transfer any amount of tokens, and the allowance isn't decremented by [`transfer_from`].
+pub fn approve<H: Host>(host: &H, spender: Address, amount: TokenAmount) -> Result<(), Error> {
+    pausable::requires_not_paused(host)?;
+
+    let owner = host.get_immediate_caller()?;
+
+    allowances::write_allowance(host, &owner, &spender, amount);
+
+    events::emit(
+        host,
+        Event::Approval {
+            owner,
+            spender,
+            amount,
+        },
+    );
+
+    Ok(())
+}
+
+/// Allow other address to transfer caller's tokens until the `expires_at` block time.
+///
+/// Once expired, the allowance is treated as zero.
+pub fn approve_with_expiry<H: Host>(
+    host: &H,
+    spender: Address,
+    amount: TokenAmount,
+    expires_at: u64,
+) -> Result<(), Error> {
+    pausable::requires_not_paused(host)?;
+
+    let owner = host.get_immediate_caller()?;
+
+    allowances::write_allowance_record(
+        host,
+        &owner,
+        &spender,
+        allowances::Allowance {
+            amount,
+            expires_at: Some(expires_at),
+        },
+    );
+
+    events::emit(
+        host,
+        Event::Approval {
+            owner,
+            spender,
+            amount,
+        },
+    );
+
+    Ok(())
+}
+
+/// Allow a `spender` contract to transfer caller's tokens, and calls its `on_approval_received` entry point.
+///
+/// The spender is identified by its contract package hash, and the latest version of it is called with the caller as the `owner`, the `amount` and the `data`. The approval is reverted unless the hook returns `true`.
+pub fn approve_and_call<H: Host>(
+    host: &H,
+    spender: ContractPackageHash,
+    amount: TokenAmount,
+    data: Vec<u8>,
+) -> Result<(), Error> {
+    let owner = host.get_immediate_caller()?;
+
+    approve(host, Address::from(spender), amount)?;
+
+    hooks::call_on_approval_received(host, spender, &owner, amount, data)
+}
+
+/// Atomically increases the allowance of a `spender` by `amount`.
+///
+/// This is an alternative to [`approve`] which is not affected by a race condition when changing an existing allowance.
+///
+/// An allowance granted with [`approve_with_expiry`] keeps its expiry, and once expired it can't be increased back into a valid allowance; use [`approve`] or [`approve_with_expiry`] instead.
+pub fn increase_allowance<H: Host>(
+    host: &H,
+    spender: Address,
+    amount: TokenAmount,
+) -> Result<(), Error> {
+    pausable::requires_not_paused(host)?;
+
+    let owner = host.get_immediate_caller()?;
+
+    let new_allowance = {
+        let allowance = allowances::read_allowance(host, &owner, &spender);
+        allowance.checked_add(amount).ok_or(Error::Overflow)?
+    };
+
+    allowances::update_allowance(host, &owner, &spender, new_allowance);
+
+    events::emit(
+        host,
+        Event::Approval {
+            owner,
+            spender,
+            amount: new_allowance,
+        },
+    );
+
+    Ok(())
+}
+
+/// Atomically decreases the allowance of a `spender` by `amount`.
+///
+/// This is an alternative to [`approve`] which is not affected by a race condition when changing an existing allowance.
+pub fn decrease_allowance<H: Host>(
+    host: &H,
+    spender: Address,
+    amount: TokenAmount,
+) -> Result<(), Error> {
+    pausable::requires_not_paused(host)?;
+
+    let owner = host.get_immediate_caller()?;
+
+    let new_allowance = {
+        let allowance = allowances::read_allowance(host, &owner, &spender);
+        allowance.checked_sub(amount).ok_or(Error::Underflow)?
+    };
+
+    allowances::update_allowance(host, &owner, &spender, new_allowance);
+
+    events::emit(
+        host,
+        Event::Approval {
+            owner,
+            spender,
+            amount: new_allowance,
+        },
+    );
+
+    Ok(())
+}
+
+/// Sets the allowance of a `spender` over the tokens of an owner, given the owner's signature.
+///
+/// The `signature` has to be made by `owner_public_key` over the message created by [`permit::make_permit_message`]. The permit is rejected after the `deadline` block time, or if the `nonce` doesn't match the current nonce of the owner, which is incremented on success.
+pub fn permit<H: Host>(
+    host: &H,
+    owner_public_key: PublicKey,
+    spender: Address,
+    amount: TokenAmount,
+    deadline: u64,
+    nonce: u64,
+    signature: Vec<u8>,
+) -> Result<(), Error> {
+    pausable::requires_not_paused(host)?;
+
+    if host.get_blocktime() > deadline {
+        return Err(Error::PermitExpired);
+    }
+
+    let owner = Address::from(owner_public_key.to_account_hash());
+
+    if nonce != permit::read_nonce(host, &owner) {
+        return Err(Error::InvalidNonce);
+    }
+
+    let message =
+        permit::make_permit_message(host, &owner_public_key, &spender, amount, deadline, nonce)?;
+    permit::verify_signature(&message, &signature, &owner_public_key)?;
+
+    permit::write_nonce(host, &owner, nonce + 1);
+
+    allowances::write_allowance(host, &owner, &spender, amount);
+
+    events::emit(
+        host,
+        Event::Approval {
+            owner,
+            spender,
+            amount,
+        },
+    );
 
-    allowances::write_allowance(&owner, &spender, amount);
+    Ok(())
+}
+
+/// Returns the current permit nonce of an `owner`.
+pub fn nonces<H: Host>(host: &H, owner: Address) -> u64 {
+    permit::read_nonce(host, &owner)
+}
+
+/// Takes a snapshot of balances and total supply, and returns its id.
+///
+/// Only an address with [`ADMIN_ROLE`] is allowed to take snapshots.
+pub fn snapshot<H: Host>(host: &H) -> Result<u64, Error> {
+    access_control::require_role(host, ADMIN_ROLE)?;
+
+    let id = snapshots::take_snapshot(host);
+
+    events::emit(host, Event::Snapshot { id });
+
+    Ok(id)
+}
+
+/// Returns balance of an owner at the time a snapshot with `snapshot_id` was taken.
+pub fn balance_of_at<H: Host>(
+    host: &H,
+    owner: Address,
+    snapshot_id: u64,
+) -> Result<TokenAmount, Error> {
+    snapshots::read_balance_at(host, &owner, snapshot_id)
+}
+
+/// Returns total supply of the token at the time a snapshot with `snapshot_id` was taken.
+pub fn total_supply_at<H: Host>(host: &H, snapshot_id: u64) -> Result<TokenAmount, Error> {
+    snapshots::read_total_supply_at(host, snapshot_id)
+}
+
+/// Delegates voting power of the caller to a `delegatee`.
+///
+/// All current and future tokens of the caller count towards the voting power of the `delegatee` until the caller delegates again. It's exposed as the `delegate` entry point.
+pub fn delegate_votes<H: Host>(host: &H, delegatee: Address) -> Result<(), Error> {
+    let delegator = host.get_immediate_caller()?;
+
+    let from_delegate = votes::read_delegate(host, &delegator);
+    votes::write_delegate(host, &delegator, &delegatee);
+
+    votes::move_voting_power(
+        host,
+        from_delegate,
+        Some(delegatee),
+        balances::read_balance(host, &delegator),
+    )?;
+
+    events::emit(
+        host,
+        Event::DelegateChanged {
+            delegator,
+            from_delegate,
+            to_delegate: delegatee,
+        },
+    );
 
     Ok(())
 }
 
+/// Returns the delegatee of an `address`, or `None` if it never delegated.
+pub fn delegates<H: Host>(host: &H, address: Address) -> Option<Address> {
+    votes::read_delegate(host, &address)
+}
+
+/// Returns the current voting power of an `address`.
+pub fn get_votes<H: Host>(host: &H, address: Address) -> TokenAmount {
+    votes::read_votes(host, &address)
+}
+
+/// Returns the voting power of an `address` at the end of a past block time.
+pub fn get_past_votes<H: Host>(
+    host: &H,
+    address: Address,
+    timestamp: u64,
+) -> Result<TokenAmount, Error> {
+    votes::read_past_votes(host, &address, timestamp)
+}
+
 /// Returns the amount allowed to spend.
-pub fn allowance(owner: AccountHash, spender: AccountHash) -> U512 {
-    allowances::read_allowance(&owner, &spender)
+///
+/// An allowance equal to [`allowances::UNLIMITED_ALLOWANCE`] is unlimited.
+pub fn allowance<H: Host>(host: &H, owner: Address, spender: Address) -> TokenAmount {
+    allowances::read_allowance(host, &owner, &spender)
 }
 
 /// Transfer tokens from `owner` address to the `recipient` address if required `amount` was approved before to be spend by the direct caller.
 ///
-/// This operation should decrement approved amount on the `owner`, and increase balance on the `recipient`.
-pub fn transfer_from(
-    owner: AccountHash,
-    recipient: AccountHash,
-    amount: U512,
+/// This operation should decrement approved amount on the `owner`, and increase balance on the `recipient`. An [`allowances::UNLIMITED_ALLOWANCE`] is never decremented.
+pub fn transfer_from<H: Host>(
+    host: &H,
+    owner: Address,
+    recipient: Address,
+    amount: TokenAmount,
 ) -> Result<(), Error> {
-    let spender = detail::get_immediate_caller()?;
+    let spender = host.get_immediate_caller()?;
+
+    frozen::requires_not_frozen(host, &spender)?;
 
-    let new_spender_allowance = {
-        let spender_allowance = allowances::read_allowance(&owner, &spender);
-        spender_allowance
+    let spender_allowance = allowances::read_allowance(host, &owner, &spender);
+
+    let new_spender_allowance = if spender_allowance == allowances::UNLIMITED_ALLOWANCE {
+        None
+    } else {
+        let new_spender_allowance = spender_allowance
             .checked_sub(amount)
-            .ok_or(Error::InsufficientAllowance)?
+            .ok_or(Error::InsufficientAllowance)?;
+        Some(new_spender_allowance)
     };
 
-    balances::transfer_balance(&owner, &recipient, amount)?;
+    balances::transfer_balance(host, &owner, &recipient, amount)?;
+
+    if let Some(new_spender_allowance) = new_spender_allowance {
+        allowances::update_allowance(host, &owner, &spender, new_spender_allowance);
+    }
+
+    Ok(())
+}
+
+/// Returns the maximum amount of tokens available for a flash loan.
+pub fn max_flash_loan<H: Host>(host: &H) -> TokenAmount {
+    flash_mint::max_flash_loan(host)
+}
+
+/// Returns the fee charged for a flash loan of `amount`.
+pub fn flash_fee(amount: TokenAmount) -> TokenAmount {
+    flash_mint::flash_fee(amount)
+}
+
+/// Lends `amount` of tokens to a `receiver` contract for the duration of its `on_flash_loan` entry point.
+///
+/// The receiver is identified by its contract package hash, as that's the address its tokens are held at, and the latest version of it is called. Its `on_flash_loan` entry point is called with the caller as the `initiator`, the `amount`, the `fee` and the `data`, and it has to return `true` and hold `amount + fee` tokens when it returns.
+pub fn flash_loan<H: Host>(
+    host: &H,
+    receiver: ContractPackageHash,
+    amount: TokenAmount,
+    data: Vec<u8>,
+) -> Result<(), Error> {
+    let initiator = host.get_immediate_caller()?;
+
+    flash_mint::flash_loan(host, &initiator, receiver, amount, data)
+}
+
+/// Deposits `amount` of motes from a `purse` provided by the caller, and mints the same amount of tokens to the caller.
+///
+/// The token gets full access to the `purse`, so it should be a purse created and funded with the `amount` for this deposit, rather than the main purse of the caller.
+pub fn deposit<H: Host>(host: &H, purse: URef, amount: TokenAmount) -> Result<(), Error> {
+    let owner = host.get_immediate_caller()?;
+
+    wrapped::deposit(host, &owner, purse, amount)
+}
+
+/// Burns `amount` of tokens of the caller, and sends the same amount of CSPR back to the caller's account.
+pub fn withdraw<H: Host>(host: &H, amount: TokenAmount) -> Result<(), Error> {
+    let owner = host.get_immediate_caller()?;
+
+    wrapped::withdraw(host, &owner, amount)
+}
+
+/// Mints `amount` of tokens to the `owner` address.
+///
+/// Only an address with [`MINTER_ROLE`] is allowed to mint tokens.
+pub fn mint<H: Host>(host: &H, owner: Address, amount: TokenAmount) -> Result<(), Error> {
+    access_control::require_role(host, MINTER_ROLE)?;
+
+    internal::mint(host, &owner, amount)
+}
+
+/// Burns `amount` of tokens of the `owner` address.
+///
+/// Only an address with [`MINTER_ROLE`] is allowed to burn tokens.
+pub fn burn<H: Host>(host: &H, owner: Address, amount: TokenAmount) -> Result<(), Error> {
+    access_control::require_role(host, MINTER_ROLE)?;
+
+    internal::burn(host, &owner, amount)
+}
+
+/// Grants a `role` to an `address`.
+///
+/// Only an address with the admin role of the `role` is allowed to grant it.
+pub fn grant_role<H: Host>(host: &H, role: String, address: Address) -> Result<(), Error> {
+    access_control::grant_role(host, &role, &address)
+}
+
+/// Revokes a `role` from an `address`.
+///
+/// Only an address with the admin role of the `role` is allowed to revoke it.
+pub fn revoke_role<H: Host>(host: &H, role: String, address: Address) -> Result<(), Error> {
+    access_control::revoke_role(host, &role, &address)
+}
+
+/// Renounces a `role` of the caller.
+///
+/// The `address` has to match the caller.
+pub fn renounce_role<H: Host>(host: &H, role: String, address: Address) -> Result<(), Error> {
+    access_control::renounce_role(host, &role, &address)
+}
+
+/// Checks if an `address` has a `role`.
+pub fn has_role<H: Host>(host: &H, role: String, address: Address) -> bool {
+    access_control::has_role(host, &role, &address)
+}
+
+/// Returns the owner of the contract, or `None` if the ownership was renounced.
+pub fn owner<H: Host>(host: &H) -> Option<Address> {
+    ownable::read_owner(host)
+}
+
+/// Starts a transfer of the ownership to a `new_owner`.
+///
+/// Only the owner is allowed to transfer the ownership, and the transfer completes once the `new_owner` calls [`accept_ownership`].
+pub fn transfer_ownership<H: Host>(host: &H, new_owner: Address) -> Result<(), Error> {
+    detail::only_owner(host)?;
+
+    ownable::write_pending_owner(host, Some(new_owner));
 
-    allowances::write_allowance(&owner, &spender, new_spender_allowance);
+    Ok(())
+}
+
+/// Accepts a pending transfer of the ownership.
+///
+/// Only the pending owner is allowed to accept the ownership.
+pub fn accept_ownership<H: Host>(host: &H) -> Result<(), Error> {
+    let caller = host.get_immediate_caller()?;
+
+    if ownable::read_pending_owner(host) != Some(caller) {
+        return Err(Error::Unauthorized);
+    }
+
+    ownable::write_owner(host, Some(caller));
+    ownable::write_pending_owner(host, None);
+
+    Ok(())
+}
+
+/// Leaves the contract without an owner.
+///
+/// Only the owner is allowed to renounce the ownership, and any pending transfer of the ownership is cancelled.
+pub fn renounce_ownership<H: Host>(host: &H) -> Result<(), Error> {
+    detail::only_owner(host)?;
+
+    ownable::write_owner(host, None);
+    ownable::write_pending_owner(host, None);
+
+    Ok(())
+}
+
+/// Freezes an `address`, so it can neither send nor receive tokens.
+///
+/// Only an address with [`ADMIN_ROLE`] is allowed to freeze addresses.
+pub fn freeze<H: Host>(host: &H, address: Address) -> Result<(), Error> {
+    access_control::require_role(host, ADMIN_ROLE)?;
+
+    frozen::write_frozen(host, &address, true);
+
+    Ok(())
+}
+
+/// Unfreezes an `address`.
+///
+/// Only an address with [`ADMIN_ROLE`] is allowed to unfreeze addresses.
+pub fn unfreeze<H: Host>(host: &H, address: Address) -> Result<(), Error> {
+    access_control::require_role(host, ADMIN_ROLE)?;
+
+    frozen::write_frozen(host, &address, false);
+
+    Ok(())
+}
+
+/// Checks if an `address` is frozen.
+pub fn is_frozen<H: Host>(host: &H, address: Address) -> bool {
+    frozen::is_frozen(host, &address)
+}
+
+/// Sets the transfer fee to `fee_basis_points` of every transfer, paid to a `fee_recipient`.
+///
+/// Only an address with [`ADMIN_ROLE`] is allowed to set the fee, and setting it to 0 basis points disables it.
+pub fn set_transfer_fee<H: Host>(
+    host: &H,
+    fee_basis_points: u32,
+    fee_recipient: Address,
+) -> Result<(), Error> {
+    access_control::require_role(host, ADMIN_ROLE)?;
+
+    fee::write_fee_policy(host, fee_basis_points, &fee_recipient)
+}
+
+/// Exempts an `address` from the transfer fee, or revokes its exemption.
+///
+/// Only an address with [`ADMIN_ROLE`] is allowed to change exemptions.
+pub fn set_fee_exempt<H: Host>(host: &H, address: Address, exempt: bool) -> Result<(), Error> {
+    access_control::require_role(host, ADMIN_ROLE)?;
+
+    fee::write_fee_exempt(host, &address, exempt);
+
+    Ok(())
+}
+
+/// Returns the transfer fee in basis points.
+pub fn fee_basis_points<H: Host>(host: &H) -> u32 {
+    fee::read_fee_basis_points(host)
+}
+
+/// Returns the recipient of the transfer fee, or `None` if the fee was never set.
+pub fn fee_recipient<H: Host>(host: &H) -> Option<Address> {
+    fee::read_fee_recipient(host)
+}
+
+/// Checks if an `address` is exempt from the transfer fee.
+pub fn is_fee_exempt<H: Host>(host: &H, address: Address) -> bool {
+    fee::is_fee_exempt(host, &address)
+}
+
+/// Locks `amount` of the caller's tokens for a `beneficiary`, vesting from `start` to `start + duration` after a `cliff`.
+///
+/// Only an address with [`ADMIN_ROLE`] is allowed to create vesting schedules.
+pub fn create_vesting_schedule<H: Host>(
+    host: &H,
+    beneficiary: Address,
+    amount: TokenAmount,
+    start: u64,
+    cliff: u64,
+    duration: u64,
+) -> Result<(), Error> {
+    access_control::require_role(host, ADMIN_ROLE)?;
+
+    let sender = host.get_immediate_caller()?;
+
+    vesting::lock(host, &sender, &beneficiary, amount, start, cliff, duration)
+}
+
+/// Releases tokens vested so far to the caller, and returns the released amount.
+pub fn release<H: Host>(host: &H) -> Result<TokenAmount, Error> {
+    let beneficiary = host.get_immediate_caller()?;
+
+    vesting::release(host, &beneficiary)
+}
+
+/// Returns the amount of tokens vested so far for a `beneficiary`, including already released tokens.
+pub fn vested_amount<H: Host>(host: &H, beneficiary: Address) -> Result<TokenAmount, Error> {
+    match vesting::read_vesting_schedule(host, &beneficiary) {
+        Some(vesting_schedule) => vesting_schedule.vested_amount(host.get_blocktime()),
+        None => Ok(TokenAmount::zero()),
+    }
+}
+
+/// Returns the amount of tokens vested so far for a `beneficiary`, that can be released.
+pub fn releasable_amount<H: Host>(host: &H, beneficiary: Address) -> Result<TokenAmount, Error> {
+    match vesting::read_vesting_schedule(host, &beneficiary) {
+        Some(vesting_schedule) => vesting_schedule.releasable_amount(host.get_blocktime()),
+        None => Ok(TokenAmount::zero()),
+    }
+}
+
+/// Pauses the token.
+///
+/// Only an address with [`PAUSER_ROLE`] is allowed to pause the token.
+pub fn pause<H: Host>(host: &H) -> Result<(), Error> {
+    access_control::require_role(host, PAUSER_ROLE)?;
+
+    pausable::write_paused(host, true);
+
+    Ok(())
+}
+
+/// Unpauses the token.
+///
+/// Only an address with [`PAUSER_ROLE`] is allowed to unpause the token.
+pub fn unpause<H: Host>(host: &H) -> Result<(), Error> {
+    access_control::require_role(host, PAUSER_ROLE)?;
+
+    pausable::write_paused(host, false);
 
     Ok(())
 }
@@ -95,16 +693,37 @@ pub fn transfer_from(
 ///
 /// It should be called from within `fn call` of your contract.
 /// TODO: since it mentions `of your contract` we can perhaps turn `bin/main` into ./examples
+#[cfg(feature = "casper")]
 pub fn delegate(
     name: String,
     symbol: String,
     decimals: u8,
-    initial_supply: U512,
+    initial_supply: TokenAmount,
+    mintable: bool,
+    wrapped: bool,
+    cap: Option<TokenAmount>,
 ) -> Result<(), Error> {
     // Only a session code can call into this, and attempt to call this function from within stored contracts of any type will raise an error.
     detail::requires_session_code()?;
 
-    let entry_points = entry_points::get_entry_points();
+    if matches!(cap, Some(cap) if initial_supply > cap) {
+        return Err(Error::CapExceeded);
+    }
+
+    if wrapped {
+        wrapped::requires_valid_wrapped_token(decimals, initial_supply, mintable)?;
+    }
+
+    let contract_key = detail::make_installer_key(&name, CONTRACT_KEY);
+    let contract_package_key = detail::make_installer_key(&name, CONTRACT_PACKAGE_KEY);
+    let contract_access_key = detail::make_installer_key(&name, CONTRACT_ACCESS_KEY);
+    let contract_wrapped_key = detail::make_installer_key(&name, CONTRACT_WRAPPED_KEY);
+
+    if runtime::has_key(&contract_package_key) {
+        return Err(Error::TokenAlreadyInstalled);
+    }
+
+    let entry_points = entry_points::get_entry_points(mintable, wrapped);
 
     let named_keys = {
         let mut named_keys = NamedKeys::new();
@@ -124,16 +743,47 @@ pub fn delegate(
             Key::from(decimals_uref)
         };
 
+        let cap_key = {
+            let cap_uref = storage::new_uref(cap).into_read();
+            Key::from(cap_uref)
+        };
+
         let total_supply_key = {
-            let total_supply_uref = storage::new_uref(initial_supply).into_read();
+            // Total supply is writable by the contract, so it can be updated on mint and burn.
+            let total_supply_uref = storage::new_uref(initial_supply);
             Key::from(total_supply_uref)
         };
 
+        let initial_owner = Address::from(runtime::get_caller());
+
+        let (owner_key, pending_owner_key) = {
+            // Installer of the token is the owner.
+            let owner_uref = storage::new_uref(Some(initial_owner));
+            let pending_owner_uref = storage::new_uref(Option::<Address>::None);
+
+            (Key::from(owner_uref), Key::from(pending_owner_uref))
+        };
+
+        let paused_key = {
+            let paused_uref = storage::new_uref(false);
+            Key::from(paused_uref)
+        };
+
+        let snapshot_id_key = {
+            let snapshot_id_uref = storage::new_uref(0u64);
+            Key::from(snapshot_id_uref)
+        };
+
         let balances_dictionary_key = {
             let balances_uref = storage::new_dictionary(BALANCES_KEY).unwrap_or_revert();
 
             // Sets up initial balance for the caller.
-            balances::write_balance_into(balances_uref, &runtime::get_caller(), initial_supply);
+            balances::write_balance_into(
+                &CasperHost,
+                balances_uref,
+                &initial_owner,
+                initial_supply,
+            );
 
             runtime::remove_key(BALANCES_KEY);
 
@@ -147,21 +797,285 @@ pub fn delegate(
             Key::from(allowance_uref)
         };
 
+        let nonces_dictionary_key = {
+            let nonces_uref = storage::new_dictionary(NONCES_KEY).unwrap_or_revert();
+            runtime::remove_key(NONCES_KEY);
+
+            Key::from(nonces_uref)
+        };
+
+        let (account_snapshots_dictionary_key, total_supply_snapshots_dictionary_key) = {
+            let account_snapshots_uref =
+                storage::new_dictionary(ACCOUNT_SNAPSHOTS_KEY).unwrap_or_revert();
+            runtime::remove_key(ACCOUNT_SNAPSHOTS_KEY);
+
+            let total_supply_snapshots_uref =
+                storage::new_dictionary(TOTAL_SUPPLY_SNAPSHOTS_KEY).unwrap_or_revert();
+            runtime::remove_key(TOTAL_SUPPLY_SNAPSHOTS_KEY);
+
+            (
+                Key::from(account_snapshots_uref),
+                Key::from(total_supply_snapshots_uref),
+            )
+        };
+
+        let (delegates_dictionary_key, vote_checkpoints_dictionary_key) = {
+            let delegates_uref = storage::new_dictionary(DELEGATES_KEY).unwrap_or_revert();
+            runtime::remove_key(DELEGATES_KEY);
+
+            let vote_checkpoints_uref =
+                storage::new_dictionary(VOTE_CHECKPOINTS_KEY).unwrap_or_revert();
+            runtime::remove_key(VOTE_CHECKPOINTS_KEY);
+
+            (Key::from(delegates_uref), Key::from(vote_checkpoints_uref))
+        };
+
+        let (fee_basis_points_key, fee_recipient_key, fee_exemptions_dictionary_key) = {
+            // Transfer fee is disabled until it is set by an admin.
+            let fee_basis_points_uref = storage::new_uref(0u32);
+            let fee_recipient_uref = storage::new_uref(Option::<Address>::None);
+
+            let fee_exemptions_uref =
+                storage::new_dictionary(FEE_EXEMPTIONS_KEY).unwrap_or_revert();
+            runtime::remove_key(FEE_EXEMPTIONS_KEY);
+
+            (
+                Key::from(fee_basis_points_uref),
+                Key::from(fee_recipient_uref),
+                Key::from(fee_exemptions_uref),
+            )
+        };
+
+        let vesting_schedules_dictionary_key = {
+            let vesting_schedules_uref =
+                storage::new_dictionary(VESTING_SCHEDULES_KEY).unwrap_or_revert();
+            runtime::remove_key(VESTING_SCHEDULES_KEY);
+
+            Key::from(vesting_schedules_uref)
+        };
+
+        let frozen_accounts_dictionary_key = {
+            let frozen_accounts_uref =
+                storage::new_dictionary(FROZEN_ACCOUNTS_KEY).unwrap_or_revert();
+            runtime::remove_key(FROZEN_ACCOUNTS_KEY);
+
+            Key::from(frozen_accounts_uref)
+        };
+
+        let (events_dictionary_key, event_count_key) = {
+            let events_uref = storage::new_dictionary(EVENTS_KEY).unwrap_or_revert();
+            runtime::remove_key(EVENTS_KEY);
+
+            let event_count_uref = storage::new_uref(0u64);
+
+            // Initial supply is announced as tokens minted to the caller.
+            events::emit_into(
+                &CasperHost,
+                events_uref,
+                event_count_uref,
+                Event::Transfer {
+                    from: None,
+                    to: Some(initial_owner),
+                    amount: initial_supply,
+                },
+            );
+
+            (Key::from(events_uref), Key::from(event_count_uref))
+        };
+
+        let (roles_dictionary_key, role_admins_dictionary_key) = {
+            let roles_uref = storage::new_dictionary(ROLES_KEY).unwrap_or_revert();
+
+            // Installer of the token is granted all the roles.
+            access_control::write_role_into(
+                &CasperHost,
+                roles_uref,
+                ADMIN_ROLE,
+                &initial_owner,
+                true,
+            );
+            access_control::write_role_into(
+                &CasperHost,
+                roles_uref,
+                PAUSER_ROLE,
+                &initial_owner,
+                true,
+            );
+            if mintable {
+                access_control::write_role_into(
+                    &CasperHost,
+                    roles_uref,
+                    MINTER_ROLE,
+                    &initial_owner,
+                    true,
+                );
+            }
+
+            runtime::remove_key(ROLES_KEY);
+
+            let role_admins_uref = storage::new_dictionary(ROLE_ADMINS_KEY).unwrap_or_revert();
+            runtime::remove_key(ROLE_ADMINS_KEY);
+
+            (Key::from(roles_uref), Key::from(role_admins_uref))
+        };
+
         named_keys.insert(NAME_KEY.to_string(), name_key);
         named_keys.insert(SYMBOL_KEY.to_string(), symbol_key);
         named_keys.insert(DECIMALS_KEY.to_string(), decimals_key);
         named_keys.insert(BALANCES_KEY.to_string(), balances_dictionary_key);
         named_keys.insert(ALLOWANCES_KEY.to_string(), allowances_dictionary_key);
+        named_keys.insert(NONCES_KEY.to_string(), nonces_dictionary_key);
         named_keys.insert(TOTAL_SUPPLY_KEY.to_string(), total_supply_key);
+        named_keys.insert(CAP_KEY.to_string(), cap_key);
+        named_keys.insert(EVENTS_KEY.to_string(), events_dictionary_key);
+        named_keys.insert(EVENT_COUNT_KEY.to_string(), event_count_key);
+        named_keys.insert(PAUSED_KEY.to_string(), paused_key);
+        named_keys.insert(
+            FROZEN_ACCOUNTS_KEY.to_string(),
+            frozen_accounts_dictionary_key,
+        );
+        named_keys.insert(FEE_BASIS_POINTS_KEY.to_string(), fee_basis_points_key);
+        named_keys.insert(FEE_RECIPIENT_KEY.to_string(), fee_recipient_key);
+        named_keys.insert(
+            FEE_EXEMPTIONS_KEY.to_string(),
+            fee_exemptions_dictionary_key,
+        );
+        named_keys.insert(
+            VESTING_SCHEDULES_KEY.to_string(),
+            vesting_schedules_dictionary_key,
+        );
+        named_keys.insert(OWNER_KEY.to_string(), owner_key);
+        named_keys.insert(PENDING_OWNER_KEY.to_string(), pending_owner_key);
+        named_keys.insert(ROLES_KEY.to_string(), roles_dictionary_key);
+        named_keys.insert(ROLE_ADMINS_KEY.to_string(), role_admins_dictionary_key);
+        named_keys.insert(SNAPSHOT_ID_KEY.to_string(), snapshot_id_key);
+        named_keys.insert(
+            ACCOUNT_SNAPSHOTS_KEY.to_string(),
+            account_snapshots_dictionary_key,
+        );
+        named_keys.insert(
+            TOTAL_SUPPLY_SNAPSHOTS_KEY.to_string(),
+            total_supply_snapshots_dictionary_key,
+        );
+        named_keys.insert(DELEGATES_KEY.to_string(), delegates_dictionary_key);
+        named_keys.insert(
+            VOTE_CHECKPOINTS_KEY.to_string(),
+            vote_checkpoints_dictionary_key,
+        );
+
+        if wrapped {
+            // Purse holds CSPR deposited in exchange for tokens.
+            let purse_uref = system::create_purse();
+            named_keys.insert(PURSE_KEY.to_string(), Key::from(purse_uref));
+        }
 
         named_keys
     };
 
+    let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
+
+    // Access to the contract package stays with the installer, so new versions can be added later.
+    runtime::put_key(&contract_package_key, Key::from(contract_package_hash));
+    runtime::put_key(&contract_access_key, Key::from(access_uref));
+
+    // Wrapping can't be turned on or off by an upgrade, as only tokens installed as wrapped are backed by CSPR.
+    let wrapped_uref = storage::new_uref(wrapped).into_read();
+    runtime::put_key(&contract_wrapped_key, Key::from(wrapped_uref));
+
     let (contract_hash, _version) =
-        storage::new_locked_contract(entry_points, Some(named_keys), None, None);
+        storage::add_contract_version(contract_package_hash, entry_points, named_keys);
 
     // Hash of the installed contract will be reachable through named keys.
-    runtime::put_key(CONTRACT_KEY, Key::from(contract_hash));
+    runtime::put_key(&contract_key, Key::from(contract_hash));
+
+    Ok(())
+}
+
+/// Upgrades the token installed by the caller by adding a new contract version to its contract package.
+///
+/// New version reuses named keys of the previous version, so metadata, balances and allowances are preserved. Previous version is disabled. A wrapped token stays wrapped, and it fails with [`Error::InvalidWrappedToken`] if it would become `mintable`.
+///
+/// It should be called from within `fn call` of your contract by the account that installed the token through [`delegate`], with the `name` the token was installed with.
+#[cfg(feature = "casper")]
+pub fn upgrade(name: &str, mintable: bool) -> Result<(), Error> {
+    detail::requires_session_code()?;
+
+    let contract_key = detail::make_installer_key(name, CONTRACT_KEY);
+
+    let contract_package_hash =
+        runtime::get_key(&detail::make_installer_key(name, CONTRACT_PACKAGE_KEY))
+            .and_then(Key::into_hash)
+            .map(ContractPackageHash::new)
+            .unwrap_or_revert();
+
+    let previous_contract_hash = runtime::get_key(&contract_key)
+        .and_then(Key::into_hash)
+        .map(ContractHash::new)
+        .unwrap_or_revert();
+
+    let wrapped: bool = detail::read_from(
+        &CasperHost,
+        &detail::make_installer_key(name, CONTRACT_WRAPPED_KEY),
+    );
+    if wrapped && mintable {
+        return Err(Error::InvalidWrappedToken);
+    }
+
+    let entry_points = entry_points::get_entry_points(mintable, wrapped);
+
+    let (contract_hash, _version) =
+        storage::add_contract_version(contract_package_hash, entry_points, NamedKeys::new());
+
+    storage::disable_contract_version(contract_package_hash, previous_contract_hash)
+        .unwrap_or_revert();
+
+    runtime::put_key(&contract_key, Key::from(contract_hash));
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use casper_types::account::AccountHash;
+
+    use super::*;
+    use crate::{
+        constants::{ARG_AMOUNT, METHOD_ON_TRANSFER_RECEIVED},
+        mock::MockHost,
+    };
+
+    const ALICE: Address = Address::Account(AccountHash::new([1; 32]));
+    const TREASURY: Address = Address::Account(AccountHash::new([3; 32]));
+    const RECEIVER: ContractPackageHash = ContractPackageHash::new([4; 32]);
+
+    #[test]
+    fn should_report_amount_received_after_fee_to_transfer_hook() {
+        let host = MockHost::new();
+        internal::mint(&host, &ALICE, TokenAmount::from(1000)).unwrap();
+        fee::write_fee_policy(&host, 250, &TREASURY).unwrap();
+        host.set_contract_result(RECEIVER, METHOD_ON_TRANSFER_RECEIVED, true);
+        host.set_caller(ALICE);
+
+        transfer_and_call(&host, RECEIVER, TokenAmount::from(400), vec![]).unwrap();
+
+        let received_amount = TokenAmount::from(390);
+        assert_eq!(
+            balances::read_balance(&host, &Address::from(RECEIVER)),
+            received_amount
+        );
+
+        let calls = host.contract_calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].entry_point_name, METHOD_ON_TRANSFER_RECEIVED);
+        let reported_amount: TokenAmount = calls[0]
+            .runtime_args
+            .get(ARG_AMOUNT)
+            .cloned()
+            .unwrap()
+            .into_t()
+            .unwrap();
+        assert_eq!(reported_amount, received_amount);
+    }
+}
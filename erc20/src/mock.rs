@@ -0,0 +1,262 @@
+//! Implementation of an in-memory host for unit tests.
+//!
+//! [`MockHost`] runs the library natively without a Casper node, so its logic can be tested with `cargo test -p erc20 --no-default-features`. It's available in the crate's own tests, and to other crates through the `test-support` feature.
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::cell::{Cell, RefCell};
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    crypto, AccessRights, ApiError, CLTyped, ContractPackageHash, RuntimeArgs, URef, U512,
+};
+
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    constants::{
+        CAP_KEY, EVENT_COUNT_KEY, FEE_BASIS_POINTS_KEY, FEE_RECIPIENT_KEY, OWNER_KEY, PAUSED_KEY,
+        PENDING_OWNER_KEY, SNAPSHOT_ID_KEY, TOTAL_SUPPLY_KEY,
+    },
+    error::Error,
+    host::{Host, UnwrapOrRevert as _},
+};
+
+/// Contract package hash the [`MockHost`] reports as the currently executing contract.
+pub const MOCK_CONTRACT_PACKAGE_HASH: ContractPackageHash = ContractPackageHash::new([255; 32]);
+
+/// Call of an entry point of another contract made through the [`MockHost`].
+#[derive(Clone, Debug)]
+pub struct ContractCall {
+    /// Contract package hash of the called contract.
+    pub contract_package_hash: ContractPackageHash,
+    /// Name of the called entry point.
+    pub entry_point_name: String,
+    /// Arguments the entry point was called with.
+    pub runtime_args: RuntimeArgs,
+}
+
+/// Host that keeps contract storage in memory.
+///
+/// Every named key resolves to a [`URef`] derived only from its name, so no lookup is needed. A revert panics with the error it reverts with.
+///
+/// Other contracts aren't executed. A call to an entry point returns the result set with [`MockHost::set_contract_result`], and is recorded so its arguments can be inspected. Purses and main purses of accounts only hold balances.
+#[derive(Debug)]
+pub struct MockHost {
+    values: RefCell<BTreeMap<[u8; 32], Vec<u8>>>,
+    dictionaries: RefCell<BTreeMap<([u8; 32], String), Vec<u8>>>,
+    caller: Cell<Option<Address>>,
+    blocktime: Cell<u64>,
+    contract_results: RefCell<BTreeMap<(ContractPackageHash, String), Vec<u8>>>,
+    contract_calls: RefCell<Vec<ContractCall>>,
+    purse_balances: RefCell<BTreeMap<[u8; 32], U512>>,
+    account_balances: RefCell<BTreeMap<AccountHash, U512>>,
+}
+
+impl MockHost {
+    /// Creates a host with the state of a freshly installed token with no supply.
+    pub fn new() -> Self {
+        let host = MockHost {
+            values: RefCell::new(BTreeMap::new()),
+            dictionaries: RefCell::new(BTreeMap::new()),
+            caller: Cell::new(None),
+            blocktime: Cell::new(0),
+            contract_results: RefCell::new(BTreeMap::new()),
+            contract_calls: RefCell::new(Vec::new()),
+            purse_balances: RefCell::new(BTreeMap::new()),
+            account_balances: RefCell::new(BTreeMap::new()),
+        };
+
+        host.write(host.get_uref(TOTAL_SUPPLY_KEY), TokenAmount::zero());
+        host.write(host.get_uref(CAP_KEY), Option::<TokenAmount>::None);
+        host.write(host.get_uref(PAUSED_KEY), false);
+        host.write(host.get_uref(SNAPSHOT_ID_KEY), 0u64);
+        host.write(host.get_uref(FEE_BASIS_POINTS_KEY), 0u32);
+        host.write(host.get_uref(FEE_RECIPIENT_KEY), Option::<Address>::None);
+        host.write(host.get_uref(EVENT_COUNT_KEY), 0u64);
+        host.write(host.get_uref(OWNER_KEY), Option::<Address>::None);
+        host.write(host.get_uref(PENDING_OWNER_KEY), Option::<Address>::None);
+
+        host
+    }
+
+    /// Sets the immediate caller of subsequent calls.
+    pub fn set_caller(&self, caller: Address) {
+        self.caller.set(Some(caller));
+    }
+
+    /// Sets the current block time.
+    pub fn set_blocktime(&self, blocktime: u64) {
+        self.blocktime.set(blocktime);
+    }
+
+    /// Sets the `result` returned by an entry point of a contract package.
+    pub fn set_contract_result<T: CLTyped + ToBytes>(
+        &self,
+        contract_package_hash: ContractPackageHash,
+        entry_point_name: &str,
+        result: T,
+    ) {
+        let bytes = result.into_bytes().unwrap_or_revert_on(self);
+        self.contract_results
+            .borrow_mut()
+            .insert((contract_package_hash, entry_point_name.into()), bytes);
+    }
+
+    /// Returns every call of an entry point of another contract, in the order they were made.
+    pub fn contract_calls(&self) -> Vec<ContractCall> {
+        self.contract_calls.borrow().clone()
+    }
+
+    /// Sets the balance of a `purse`.
+    pub fn set_purse_balance(&self, purse: URef, balance: U512) {
+        self.purse_balances
+            .borrow_mut()
+            .insert(purse.addr(), balance);
+    }
+
+    /// Returns the balance of a `purse`.
+    pub fn purse_balance(&self, purse: URef) -> U512 {
+        let purse_balances = self.purse_balances.borrow();
+        purse_balances
+            .get(&purse.addr())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the balance of the main purse of an account.
+    pub fn account_balance(&self, account_hash: AccountHash) -> U512 {
+        let account_balances = self.account_balances.borrow();
+        account_balances
+            .get(&account_hash)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Takes `amount` of motes out of a `source` purse.
+    fn withdraw_from_purse(&self, source: URef, amount: U512) -> Result<(), ApiError> {
+        let balance = self.purse_balance(source);
+        let new_balance = balance.checked_sub(amount).ok_or(ApiError::Transfer)?;
+        self.set_purse_balance(source, new_balance);
+        Ok(())
+    }
+}
+
+impl Default for MockHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Host for MockHost {
+    fn get_uref(&self, name: &str) -> URef {
+        URef::new(crypto::blake2b(name), AccessRights::READ_ADD_WRITE)
+    }
+
+    fn read<T: CLTyped + FromBytes>(&self, uref: URef) -> Option<T> {
+        let bytes = self.values.borrow().get(&uref.addr()).cloned()?;
+        Some(bytesrepr::deserialize(bytes).unwrap_or_revert_on(self))
+    }
+
+    fn write<T: CLTyped + ToBytes>(&self, uref: URef, value: T) {
+        let bytes = value.into_bytes().unwrap_or_revert_on(self);
+        self.values.borrow_mut().insert(uref.addr(), bytes);
+    }
+
+    fn dictionary_get<T: CLTyped + FromBytes>(
+        &self,
+        dictionary_uref: URef,
+        dictionary_item_key: &str,
+    ) -> Option<T> {
+        let bytes = self
+            .dictionaries
+            .borrow()
+            .get(&(dictionary_uref.addr(), dictionary_item_key.into()))
+            .cloned()?;
+        Some(bytesrepr::deserialize(bytes).unwrap_or_revert_on(self))
+    }
+
+    fn dictionary_put<T: CLTyped + ToBytes>(
+        &self,
+        dictionary_uref: URef,
+        dictionary_item_key: &str,
+        value: T,
+    ) {
+        let bytes = value.into_bytes().unwrap_or_revert_on(self);
+        self.dictionaries
+            .borrow_mut()
+            .insert((dictionary_uref.addr(), dictionary_item_key.into()), bytes);
+    }
+
+    fn blake2b(&self, input: &[u8]) -> [u8; 32] {
+        crypto::blake2b(input)
+    }
+
+    fn get_immediate_caller(&self) -> Result<Address, Error> {
+        self.caller.get().ok_or(Error::InvalidContext)
+    }
+
+    fn get_current_contract_package_hash(&self) -> Result<ContractPackageHash, Error> {
+        Ok(MOCK_CONTRACT_PACKAGE_HASH)
+    }
+
+    fn get_blocktime(&self) -> u64 {
+        self.blocktime.get()
+    }
+
+    fn call_versioned_contract<T: CLTyped + FromBytes>(
+        &self,
+        contract_package_hash: ContractPackageHash,
+        entry_point_name: &str,
+        runtime_args: RuntimeArgs,
+    ) -> T {
+        self.contract_calls.borrow_mut().push(ContractCall {
+            contract_package_hash,
+            entry_point_name: entry_point_name.into(),
+            runtime_args,
+        });
+
+        let bytes = self
+            .contract_results
+            .borrow()
+            .get(&(contract_package_hash, entry_point_name.into()))
+            .cloned()
+            .unwrap_or_else(|| {
+                panic!(
+                    "no result set for entry point {} of {}",
+                    entry_point_name, contract_package_hash
+                )
+            });
+        bytesrepr::deserialize(bytes).unwrap_or_revert_on(self)
+    }
+
+    fn transfer_from_purse_to_purse(
+        &self,
+        source: URef,
+        target: URef,
+        amount: U512,
+    ) -> Result<(), ApiError> {
+        self.withdraw_from_purse(source, amount)?;
+        let target_balance = self.purse_balance(target) + amount;
+        self.set_purse_balance(target, target_balance);
+        Ok(())
+    }
+
+    fn transfer_from_purse_to_account(
+        &self,
+        source: URef,
+        target: AccountHash,
+        amount: U512,
+    ) -> Result<(), ApiError> {
+        self.withdraw_from_purse(source, amount)?;
+        *self
+            .account_balances
+            .borrow_mut()
+            .entry(target)
+            .or_default() += amount;
+        Ok(())
+    }
+
+    fn revert<E: Into<ApiError>>(&self, error: E) -> ! {
+        panic!("reverted with {:?}", error.into())
+    }
+}
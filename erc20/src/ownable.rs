@@ -0,0 +1,34 @@
+//! Implementation of a contract ownership.
+//!
+//! Ownership is transferred in two steps: the current owner nominates a pending owner, and the pending owner has to accept it. This way the ownership can't be lost to a mistyped address.
+
+use crate::{
+    address::Address,
+    constants::{OWNER_KEY, PENDING_OWNER_KEY},
+    detail,
+    host::Host,
+};
+
+/// Reads the owner of the contract.
+///
+/// Returns `None` if the ownership was renounced.
+pub fn read_owner<H: Host>(host: &H) -> Option<Address> {
+    detail::read_from(host, OWNER_KEY)
+}
+
+/// Writes the owner of the contract.
+pub fn write_owner<H: Host>(host: &H, owner: Option<Address>) {
+    let owner_uref = host.get_uref(OWNER_KEY);
+    host.write(owner_uref, owner);
+}
+
+/// Reads the pending owner of the contract.
+pub fn read_pending_owner<H: Host>(host: &H) -> Option<Address> {
+    detail::read_from(host, PENDING_OWNER_KEY)
+}
+
+/// Writes the pending owner of the contract.
+pub fn write_pending_owner<H: Host>(host: &H, pending_owner: Option<Address>) {
+    let pending_owner_uref = host.get_uref(PENDING_OWNER_KEY);
+    host.write(pending_owner_uref, pending_owner);
+}
@@ -0,0 +1,37 @@
+//! Implementation of a pausable token.
+//!
+//! While the token is paused all token movements and approvals fail with [`Error::Paused`], but read-only entry points keep working.
+use casper_types::URef;
+
+use crate::{
+    constants::PAUSED_KEY,
+    error::Error,
+    host::{Host, UnwrapOrRevert as _},
+};
+
+#[inline]
+fn get_paused_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(PAUSED_KEY)
+}
+
+/// Checks if the token is paused.
+pub fn is_paused<H: Host>(host: &H) -> bool {
+    let paused_uref = get_paused_uref(host);
+    host.read(paused_uref).unwrap_or_revert_on(host)
+}
+
+/// Writes a paused flag of the token.
+pub fn write_paused<H: Host>(host: &H, paused: bool) {
+    let paused_uref = get_paused_uref(host);
+    host.write(paused_uref, paused);
+}
+
+/// Makes sure that the token is not paused.
+#[inline]
+pub fn requires_not_paused<H: Host>(host: &H) -> Result<(), Error> {
+    if is_paused(host) {
+        Err(Error::Paused)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+//! Implementation of signature-based approvals.
+//!
+//! An owner signs a permit off-chain, and anyone can submit it to set an allowance on the owner's behalf. Each permit carries a nonce that has to match the current nonce of the owner, so a signature can't be replayed.
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    crypto, PublicKey, Signature, URef,
+};
+
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    constants::NONCES_KEY,
+    detail,
+    error::Error,
+    host::{Host, UnwrapOrRevert as _},
+};
+
+/// Prefix of a permit message that separates it from signatures used for other purposes.
+const PERMIT_DOMAIN: &[u8] = b"erc20-permit";
+
+#[inline]
+fn get_nonces_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(NONCES_KEY)
+}
+
+/// Reads the current permit nonce of an owner.
+pub fn read_nonce<H: Host>(host: &H, owner: &Address) -> u64 {
+    let nonces_uref = get_nonces_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, owner);
+
+    host.dictionary_get(nonces_uref, &dictionary_item_key)
+        .unwrap_or_default()
+}
+
+/// Writes the permit nonce of an owner.
+pub fn write_nonce<H: Host>(host: &H, owner: &Address, nonce: u64) {
+    let nonces_uref = get_nonces_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, owner);
+    host.dictionary_put(nonces_uref, &dictionary_item_key, nonce);
+}
+
+/// Creates a message that has to be signed by the owner to approve a spender.
+///
+/// The message is a hash of all permit parameters prefixed with the contract package hash of the token, so a permit is valid only for a single token.
+pub fn make_permit_message<H: Host>(
+    host: &H,
+    owner_public_key: &PublicKey,
+    spender: &Address,
+    amount: TokenAmount,
+    deadline: u64,
+    nonce: u64,
+) -> Result<[u8; 32], Error> {
+    let contract_package_hash = host.get_current_contract_package_hash()?;
+
+    let mut preimage = Vec::new();
+    preimage.extend_from_slice(PERMIT_DOMAIN);
+    preimage.extend_from_slice(contract_package_hash.as_bytes());
+    preimage.append(&mut owner_public_key.to_bytes().unwrap_or_revert_on(host));
+    preimage.append(&mut spender.to_bytes().unwrap_or_revert_on(host));
+    preimage.append(&mut amount.to_bytes().unwrap_or_revert_on(host));
+    preimage.append(&mut deadline.to_bytes().unwrap_or_revert_on(host));
+    preimage.append(&mut nonce.to_bytes().unwrap_or_revert_on(host));
+
+    Ok(host.blake2b(&preimage))
+}
+
+/// Verifies a serialized Ed25519 or secp256k1 signature of a message.
+pub fn verify_signature(
+    message: &[u8],
+    signature_bytes: &[u8],
+    public_key: &PublicKey,
+) -> Result<(), Error> {
+    let signature = match Signature::from_bytes(signature_bytes) {
+        Ok((signature, remainder)) if remainder.is_empty() => signature,
+        _ => return Err(Error::InvalidSignature),
+    };
+
+    crypto::verify(message, &signature, public_key).map_err(|_| Error::InvalidSignature)
+}
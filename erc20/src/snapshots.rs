@@ -0,0 +1,235 @@
+//! Implementation of balance snapshots.
+//!
+//! Taking a snapshot only bumps the current snapshot id. Balances and total supply are recorded lazily: the first time a value changes after a snapshot, its value from before the change is stored under the current snapshot id. A value that hasn't changed since a snapshot is read from the current state.
+//!
+//! Every recorded value is stored as a separate dictionary item next to the number of values recorded, so recording a new one costs the same however long the history is.
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{bytesrepr::ToBytes, URef};
+
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    balances,
+    constants::{ACCOUNT_SNAPSHOTS_KEY, SNAPSHOT_ID_KEY, TOTAL_SUPPLY_SNAPSHOTS_KEY},
+    error::Error,
+    host::{Host, UnwrapOrRevert as _},
+    total_supply,
+};
+
+/// Value paired with the snapshot id it was recorded at.
+pub type Snapshot = (u64, TokenAmount);
+
+#[inline]
+fn get_snapshot_id_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(SNAPSHOT_ID_KEY)
+}
+
+#[inline]
+fn get_account_snapshots_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(ACCOUNT_SNAPSHOTS_KEY)
+}
+
+#[inline]
+fn get_total_supply_snapshots_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(TOTAL_SUPPLY_SNAPSHOTS_KEY)
+}
+
+/// Recorded values of either the balance of an address or the total supply.
+///
+/// The number of values is stored under an item key made of the `prefix`, and every value under an item key made of the `prefix` and its index. Preimage of a value is longer than the one of the count, so they never share an item key.
+struct History {
+    dictionary_uref: URef,
+    prefix: Vec<u8>,
+}
+
+impl History {
+    fn of_balance<H: Host>(host: &H, address: &Address) -> Self {
+        History {
+            dictionary_uref: get_account_snapshots_uref(host),
+            prefix: address.to_bytes().unwrap_or_revert_on(host),
+        }
+    }
+
+    fn of_total_supply<H: Host>(host: &H) -> Self {
+        History {
+            dictionary_uref: get_total_supply_snapshots_uref(host),
+            prefix: Vec::new(),
+        }
+    }
+
+    fn make_item_key<H: Host>(&self, host: &H, index: Option<u32>) -> String {
+        let mut preimage = self.prefix.clone();
+        if let Some(index) = index {
+            preimage.append(&mut index.to_bytes().unwrap_or_revert_on(host));
+        }
+
+        let key_bytes = host.blake2b(&preimage);
+        hex::encode(&key_bytes)
+    }
+
+    fn read_count<H: Host>(&self, host: &H) -> u32 {
+        let dictionary_item_key = self.make_item_key(host, None);
+        host.dictionary_get(self.dictionary_uref, &dictionary_item_key)
+            .unwrap_or_default()
+    }
+
+    /// Every index below [`History::read_count`] has a value, and reading any other index reverts.
+    fn read<H: Host>(&self, host: &H, index: u32) -> Snapshot {
+        let dictionary_item_key = self.make_item_key(host, Some(index));
+        host.dictionary_get(self.dictionary_uref, &dictionary_item_key)
+            .unwrap_or_revert_on(host)
+    }
+
+    /// Appends a `value` unless one was already recorded for the current snapshot.
+    fn record<H: Host>(&self, host: &H, value: TokenAmount) {
+        let current_snapshot_id = read_snapshot_id(host);
+        if current_snapshot_id == 0 {
+            return;
+        }
+
+        let count = self.read_count(host);
+        if let Some(last) = count.checked_sub(1) {
+            if self.read(host, last).0 >= current_snapshot_id {
+                return;
+            }
+        }
+
+        let snapshot_item_key = self.make_item_key(host, Some(count));
+        host.dictionary_put(
+            self.dictionary_uref,
+            &snapshot_item_key,
+            (current_snapshot_id, value),
+        );
+
+        let new_count = count
+            .checked_add(1)
+            .ok_or(Error::Overflow)
+            .unwrap_or_revert_on(host);
+        let count_item_key = self.make_item_key(host, None);
+        host.dictionary_put(self.dictionary_uref, &count_item_key, new_count);
+    }
+
+    /// Finds a value recorded at `snapshot_id`.
+    ///
+    /// Returns `None` if the value didn't change since that snapshot, which means the current value should be used. Fails with [`Error::InvalidSnapshot`] if the snapshot wasn't taken yet.
+    fn value_at<H: Host>(&self, host: &H, snapshot_id: u64) -> Result<Option<TokenAmount>, Error> {
+        if snapshot_id == 0 || snapshot_id > read_snapshot_id(host) {
+            return Err(Error::InvalidSnapshot);
+        }
+
+        // The first value recorded at or after `snapshot_id` is the value from before any change made after it was taken, so binary search for the number of values recorded before it.
+        let count = self.read_count(host);
+        let mut low = 0;
+        let mut high = count;
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let (id, _) = self.read(host, middle);
+            if id < snapshot_id {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        if low < count {
+            Ok(Some(self.read(host, low).1))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Reads the id of the most recent snapshot, or 0 if no snapshot was taken yet.
+pub fn read_snapshot_id<H: Host>(host: &H) -> u64 {
+    let snapshot_id_uref = get_snapshot_id_uref(host);
+    host.read(snapshot_id_uref).unwrap_or_revert_on(host)
+}
+
+/// Takes a new snapshot, and returns its id.
+pub fn take_snapshot<H: Host>(host: &H) -> u64 {
+    let snapshot_id = read_snapshot_id(host) + 1;
+    host.write(get_snapshot_id_uref(host), snapshot_id);
+    snapshot_id
+}
+
+/// Records the balance of an address before it is changed.
+pub fn record_balance<H: Host>(host: &H, address: &Address) {
+    History::of_balance(host, address).record(host, balances::read_balance(host, address));
+}
+
+/// Records the total supply before it is changed.
+pub fn record_total_supply<H: Host>(host: &H) {
+    History::of_total_supply(host).record(host, total_supply::read_total_supply(host));
+}
+
+/// Reads the balance of an address at the time a snapshot was taken.
+pub fn read_balance_at<H: Host>(
+    host: &H,
+    address: &Address,
+    snapshot_id: u64,
+) -> Result<TokenAmount, Error> {
+    let balance = History::of_balance(host, address).value_at(host, snapshot_id)?;
+    Ok(balance.unwrap_or_else(|| balances::read_balance(host, address)))
+}
+
+/// Reads the total supply at the time a snapshot was taken.
+pub fn read_total_supply_at<H: Host>(host: &H, snapshot_id: u64) -> Result<TokenAmount, Error> {
+    let total_supply = History::of_total_supply(host).value_at(host, snapshot_id)?;
+    Ok(total_supply.unwrap_or_else(|| total_supply::read_total_supply(host)))
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::account::AccountHash;
+
+    use super::*;
+    use crate::{internal, mock::MockHost};
+
+    const ALICE: Address = Address::Account(AccountHash::new([1; 32]));
+
+    #[test]
+    fn should_record_one_value_per_snapshot() {
+        let host = MockHost::new();
+        internal::mint(&host, &ALICE, TokenAmount::from(100)).unwrap();
+
+        take_snapshot(&host);
+        internal::mint(&host, &ALICE, TokenAmount::from(10)).unwrap();
+        internal::mint(&host, &ALICE, TokenAmount::from(10)).unwrap();
+
+        let history = History::of_balance(&host, &ALICE);
+        assert_eq!(history.read_count(&host), 1);
+        assert_eq!(history.read(&host, 0), (1, TokenAmount::from(100)));
+
+        let history = History::of_total_supply(&host);
+        assert_eq!(history.read_count(&host), 1);
+        assert_eq!(history.read(&host, 0), (1, TokenAmount::from(100)));
+    }
+
+    #[test]
+    fn should_read_values_at_snapshots() {
+        let host = MockHost::new();
+
+        for amount in [10, 20, 30, 40].iter() {
+            internal::mint(&host, &ALICE, TokenAmount::from(*amount)).unwrap();
+            take_snapshot(&host);
+            // A snapshot in which nothing changes is read from the next recorded value.
+            take_snapshot(&host);
+        }
+
+        let balance_at = |snapshot_id| read_balance_at(&host, &ALICE, snapshot_id).unwrap();
+        assert_eq!(balance_at(1), TokenAmount::from(10));
+        assert_eq!(balance_at(2), TokenAmount::from(10));
+        assert_eq!(balance_at(3), TokenAmount::from(30));
+        assert_eq!(balance_at(5), TokenAmount::from(60));
+        assert_eq!(balance_at(8), TokenAmount::from(100));
+        assert_eq!(
+            read_total_supply_at(&host, 4).unwrap(),
+            TokenAmount::from(30)
+        );
+        assert_eq!(
+            read_balance_at(&host, &ALICE, 9),
+            Err(Error::InvalidSnapshot)
+        );
+    }
+}
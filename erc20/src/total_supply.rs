@@ -0,0 +1,30 @@
+//! Implementation of total supply.
+use casper_types::URef;
+
+use crate::{
+    amount::TokenAmount,
+    constants::TOTAL_SUPPLY_KEY,
+    host::{Host, UnwrapOrRevert as _},
+    snapshots,
+};
+
+#[inline]
+fn get_total_supply_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(TOTAL_SUPPLY_KEY)
+}
+
+/// Reads a total supply of the token.
+pub fn read_total_supply<H: Host>(host: &H) -> TokenAmount {
+    let total_supply_uref = get_total_supply_uref(host);
+    host.read(total_supply_uref).unwrap_or_revert_on(host)
+}
+
+/// Writes a total supply of the token.
+///
+/// Previous total supply is recorded for the current snapshot, if it wasn't recorded already.
+pub fn write_total_supply<H: Host>(host: &H, total_supply: TokenAmount) {
+    snapshots::record_total_supply(host);
+
+    let total_supply_uref = get_total_supply_uref(host);
+    host.write(total_supply_uref, total_supply);
+}
@@ -0,0 +1,307 @@
+//! Implementation of token vesting.
+//!
+//! An admin locks tokens for a beneficiary, and they are held by the token contract itself until they vest. Nothing vests before the cliff, and after it tokens vest linearly from the start until the whole amount is vested at the end of the duration. All times are block times in milliseconds.
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, URef,
+};
+
+use crate::{
+    address::Address, amount::TokenAmount, balances, constants::VESTING_SCHEDULES_KEY, detail,
+    error::Error, host::Host,
+};
+
+/// Vesting schedule of a beneficiary.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct VestingSchedule {
+    /// Amount of tokens locked for the beneficiary.
+    pub total: TokenAmount,
+    /// Amount of tokens already released to the beneficiary.
+    pub released: TokenAmount,
+    /// Block time the vesting starts at.
+    pub start: u64,
+    /// Time after the `start` before which nothing is vested.
+    pub cliff: u64,
+    /// Time after the `start` at which everything is vested.
+    pub duration: u64,
+}
+
+impl VestingSchedule {
+    /// Returns the amount vested at a given block time.
+    pub fn vested_amount(&self, blocktime: u64) -> Result<TokenAmount, Error> {
+        let elapsed = blocktime.saturating_sub(self.start);
+
+        if elapsed < self.cliff {
+            Ok(TokenAmount::zero())
+        } else if elapsed >= self.duration {
+            Ok(self.total)
+        } else {
+            // Total is split into whole multiples of the duration and the remainder, so the intermediate products never exceed the total, or the square of the duration.
+            let duration = TokenAmount::from(self.duration);
+            let elapsed = TokenAmount::from(elapsed);
+
+            let whole = (self.total / duration)
+                .checked_mul(elapsed)
+                .ok_or(Error::Overflow)?;
+            let remainder = (self.total % duration)
+                .checked_mul(elapsed)
+                .ok_or(Error::Overflow)?
+                / duration;
+
+            whole.checked_add(remainder).ok_or(Error::Overflow)
+        }
+    }
+
+    /// Returns the amount vested at a given block time, but not released yet.
+    pub fn releasable_amount(&self, blocktime: u64) -> Result<TokenAmount, Error> {
+        let vested = self.vested_amount(blocktime)?;
+        // Released amount never exceeds the vested amount, as vesting is monotonic in time.
+        Ok(vested - self.released)
+    }
+}
+
+impl CLTyped for VestingSchedule {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for VestingSchedule {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(self.serialized_length());
+        result.append(&mut self.total.to_bytes()?);
+        result.append(&mut self.released.to_bytes()?);
+        result.append(&mut self.start.to_bytes()?);
+        result.append(&mut self.cliff.to_bytes()?);
+        result.append(&mut self.duration.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.total.serialized_length()
+            + self.released.serialized_length()
+            + self.start.serialized_length()
+            + self.cliff.serialized_length()
+            + self.duration.serialized_length()
+    }
+}
+
+impl FromBytes for VestingSchedule {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (total, remainder) = TokenAmount::from_bytes(bytes)?;
+        let (released, remainder) = TokenAmount::from_bytes(remainder)?;
+        let (start, remainder) = u64::from_bytes(remainder)?;
+        let (cliff, remainder) = u64::from_bytes(remainder)?;
+        let (duration, remainder) = u64::from_bytes(remainder)?;
+        let vesting_schedule = VestingSchedule {
+            total,
+            released,
+            start,
+            cliff,
+            duration,
+        };
+        Ok((vesting_schedule, remainder))
+    }
+}
+
+#[inline]
+fn get_vesting_schedules_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(VESTING_SCHEDULES_KEY)
+}
+
+/// Reads the vesting schedule of a beneficiary.
+pub fn read_vesting_schedule<H: Host>(host: &H, beneficiary: &Address) -> Option<VestingSchedule> {
+    let vesting_schedules_uref = get_vesting_schedules_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, beneficiary);
+
+    host.dictionary_get(vesting_schedules_uref, &dictionary_item_key)
+}
+
+/// Writes the vesting schedule of a beneficiary.
+pub fn write_vesting_schedule<H: Host>(
+    host: &H,
+    beneficiary: &Address,
+    vesting_schedule: VestingSchedule,
+) {
+    let vesting_schedules_uref = get_vesting_schedules_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, beneficiary);
+    host.dictionary_put(
+        vesting_schedules_uref,
+        &dictionary_item_key,
+        vesting_schedule,
+    );
+}
+
+/// Returns the address that holds locked tokens, which is the token contract itself.
+fn get_vesting_address<H: Host>(host: &H) -> Result<Address, Error> {
+    host.get_current_contract_package_hash().map(Address::from)
+}
+
+/// Locks `total` tokens of a `sender` for a `beneficiary`.
+///
+/// A beneficiary can only have a single vesting schedule. It fails with [`Error::VestingScheduleExists`] if the beneficiary already has one, and with [`Error::InvalidVestingSchedule`] if the `cliff` is longer than the `duration`, or the `duration` is 0.
+pub fn lock<H: Host>(
+    host: &H,
+    sender: &Address,
+    beneficiary: &Address,
+    total: TokenAmount,
+    start: u64,
+    cliff: u64,
+    duration: u64,
+) -> Result<(), Error> {
+    if read_vesting_schedule(host, beneficiary).is_some() {
+        return Err(Error::VestingScheduleExists);
+    }
+
+    if duration == 0 || cliff > duration {
+        return Err(Error::InvalidVestingSchedule);
+    }
+
+    let vesting_address = get_vesting_address(host)?;
+
+    // Locked tokens are moved without a transfer fee, so the whole amount can be released later.
+    balances::transfer_balance_without_fee(host, sender, &vesting_address, total)?;
+
+    write_vesting_schedule(
+        host,
+        beneficiary,
+        VestingSchedule {
+            total,
+            released: TokenAmount::zero(),
+            start,
+            cliff,
+            duration,
+        },
+    );
+
+    Ok(())
+}
+
+/// Releases tokens vested so far to a `beneficiary`.
+///
+/// Fails with [`Error::NoVestingSchedule`] if the beneficiary has no vesting schedule.
+pub fn release<H: Host>(host: &H, beneficiary: &Address) -> Result<TokenAmount, Error> {
+    let mut vesting_schedule =
+        read_vesting_schedule(host, beneficiary).ok_or(Error::NoVestingSchedule)?;
+
+    let releasable = vesting_schedule.releasable_amount(host.get_blocktime())?;
+    if releasable.is_zero() {
+        return Ok(releasable);
+    }
+
+    vesting_schedule.released += releasable;
+    write_vesting_schedule(host, beneficiary, vesting_schedule);
+
+    balances::transfer_balance_without_fee(
+        host,
+        &get_vesting_address(host)?,
+        beneficiary,
+        releasable,
+    )?;
+
+    Ok(releasable)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::account::AccountHash;
+
+    use super::*;
+    use crate::{fee, internal, mock::MockHost};
+
+    const ALICE: Address = Address::Account(AccountHash::new([1; 32]));
+    const BOB: Address = Address::Account(AccountHash::new([2; 32]));
+    const TREASURY: Address = Address::Account(AccountHash::new([3; 32]));
+
+    fn vesting_schedule(total: TokenAmount) -> VestingSchedule {
+        VestingSchedule {
+            total,
+            released: TokenAmount::zero(),
+            start: 10_000,
+            cliff: 1_000,
+            duration: 4_000,
+        }
+    }
+
+    #[test]
+    fn should_vest_nothing_before_cliff() {
+        let vesting_schedule = vesting_schedule(TokenAmount::from(1_000));
+
+        assert_eq!(vesting_schedule.vested_amount(0), Ok(TokenAmount::zero()));
+        assert_eq!(
+            vesting_schedule.vested_amount(10_999),
+            Ok(TokenAmount::zero())
+        );
+    }
+
+    #[test]
+    fn should_vest_linearly_after_cliff() {
+        let vesting_schedule = vesting_schedule(TokenAmount::from(1_000));
+
+        assert_eq!(
+            vesting_schedule.vested_amount(11_000),
+            Ok(TokenAmount::from(250))
+        );
+        assert_eq!(
+            vesting_schedule.vested_amount(12_001),
+            Ok(TokenAmount::from(500))
+        );
+        assert_eq!(
+            vesting_schedule.vested_amount(20_000),
+            Ok(TokenAmount::from(1_000))
+        );
+    }
+
+    #[test]
+    fn should_vest_largest_total_without_overflow() {
+        let vesting_schedule = vesting_schedule(TokenAmount::max_value());
+
+        assert_eq!(
+            vesting_schedule.vested_amount(12_000),
+            Ok(TokenAmount::max_value() / 2)
+        );
+        assert_eq!(
+            vesting_schedule.vested_amount(14_000),
+            Ok(TokenAmount::max_value())
+        );
+    }
+
+    #[test]
+    fn should_subtract_released_from_releasable_amount() {
+        let vesting_schedule = VestingSchedule {
+            released: TokenAmount::from(250),
+            ..vesting_schedule(TokenAmount::from(1_000))
+        };
+
+        assert_eq!(
+            vesting_schedule.releasable_amount(12_000),
+            Ok(TokenAmount::from(250))
+        );
+    }
+
+    #[test]
+    fn should_lock_and_release_without_fee_or_exemption() {
+        let host = MockHost::new();
+        internal::mint(&host, &ALICE, TokenAmount::from(1_000)).unwrap();
+        fee::write_fee_policy(&host, 250, &TREASURY).unwrap();
+
+        lock(&host, &ALICE, &BOB, TokenAmount::from(1_000), 0, 0, 1_000).unwrap();
+        host.set_blocktime(1_000);
+        release(&host, &BOB).unwrap();
+
+        assert_eq!(
+            balances::read_balance(&host, &BOB),
+            TokenAmount::from(1_000)
+        );
+        assert_eq!(
+            balances::read_balance(&host, &TREASURY),
+            TokenAmount::zero()
+        );
+        assert!(!fee::is_fee_exempt(
+            &host,
+            &get_vesting_address(&host).unwrap()
+        ));
+    }
+}
@@ -0,0 +1,238 @@
+//! Implementation of vote delegation.
+//!
+//! Holders delegate their voting power to a delegatee, which may be the holder itself. Tokens of a holder that never delegated carry no voting power. Voting power of every delegatee is tracked as a list of checkpoints keyed by block time, so it can be looked up at any point in the past.
+//!
+//! Every checkpoint is stored as a separate dictionary item next to the number of checkpoints of its delegatee, so recording a new one costs the same however long the history is.
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{bytesrepr::ToBytes, URef};
+
+use crate::{
+    address::Address,
+    amount::TokenAmount,
+    constants::{DELEGATES_KEY, VOTE_CHECKPOINTS_KEY},
+    detail,
+    error::Error,
+    host::{Host, UnwrapOrRevert as _},
+};
+
+/// Voting power paired with the block time it was recorded at.
+pub type Checkpoint = (u64, TokenAmount);
+
+#[inline]
+fn get_delegates_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(DELEGATES_KEY)
+}
+
+#[inline]
+fn get_vote_checkpoints_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(VOTE_CHECKPOINTS_KEY)
+}
+
+/// Creates a dictionary item key for a checkpoint at an `index` of an address.
+///
+/// Preimage is longer than the one of [`detail::make_dictionary_item_key`], so a checkpoint never shares an item key with the number of checkpoints.
+fn make_checkpoint_item_key<H: Host>(host: &H, address: &Address, index: u32) -> String {
+    let mut preimage = Vec::new();
+    preimage.append(&mut address.to_bytes().unwrap_or_revert_on(host));
+    preimage.append(&mut index.to_bytes().unwrap_or_revert_on(host));
+
+    let key_bytes = host.blake2b(&preimage);
+    hex::encode(&key_bytes)
+}
+
+/// Reads the delegatee of an address, or `None` if the address never delegated.
+pub fn read_delegate<H: Host>(host: &H, delegator: &Address) -> Option<Address> {
+    let delegates_uref = get_delegates_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, delegator);
+
+    host.dictionary_get(delegates_uref, &dictionary_item_key)
+}
+
+/// Writes the delegatee of an address.
+pub fn write_delegate<H: Host>(host: &H, delegator: &Address, delegatee: &Address) {
+    let delegates_uref = get_delegates_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, delegator);
+    host.dictionary_put(delegates_uref, &dictionary_item_key, *delegatee);
+}
+
+/// Reads the number of voting power checkpoints of an address.
+pub fn read_checkpoint_count<H: Host>(host: &H, address: &Address) -> u32 {
+    let vote_checkpoints_uref = get_vote_checkpoints_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, address);
+
+    host.dictionary_get(vote_checkpoints_uref, &dictionary_item_key)
+        .unwrap_or_default()
+}
+
+fn write_checkpoint_count<H: Host>(host: &H, address: &Address, count: u32) {
+    let vote_checkpoints_uref = get_vote_checkpoints_uref(host);
+    let dictionary_item_key = detail::make_dictionary_item_key(host, address);
+    host.dictionary_put(vote_checkpoints_uref, &dictionary_item_key, count);
+}
+
+/// Reads the voting power checkpoint at an `index` of an address.
+///
+/// Every index below [`read_checkpoint_count`] has a checkpoint, and reading any other index reverts.
+pub fn read_checkpoint<H: Host>(host: &H, address: &Address, index: u32) -> Checkpoint {
+    let vote_checkpoints_uref = get_vote_checkpoints_uref(host);
+    let dictionary_item_key = make_checkpoint_item_key(host, address, index);
+
+    host.dictionary_get(vote_checkpoints_uref, &dictionary_item_key)
+        .unwrap_or_revert_on(host)
+}
+
+fn write_checkpoint<H: Host>(host: &H, address: &Address, index: u32, checkpoint: Checkpoint) {
+    let vote_checkpoints_uref = get_vote_checkpoints_uref(host);
+    let dictionary_item_key = make_checkpoint_item_key(host, address, index);
+    host.dictionary_put(vote_checkpoints_uref, &dictionary_item_key, checkpoint);
+}
+
+/// Reads the current voting power of an address.
+pub fn read_votes<H: Host>(host: &H, address: &Address) -> TokenAmount {
+    match read_checkpoint_count(host, address).checked_sub(1) {
+        Some(last) => read_checkpoint(host, address, last).1,
+        None => TokenAmount::zero(),
+    }
+}
+
+/// Reads the voting power of an address at the end of a given block time.
+///
+/// Fails with [`Error::FutureLookup`] if the `timestamp` is not in the past, as the voting power at that time may still change.
+pub fn read_past_votes<H: Host>(
+    host: &H,
+    address: &Address,
+    timestamp: u64,
+) -> Result<TokenAmount, Error> {
+    if timestamp >= host.get_blocktime() {
+        return Err(Error::FutureLookup);
+    }
+
+    // Binary search for the number of checkpoints recorded at or before the `timestamp`.
+    let mut low = 0;
+    let mut high = read_checkpoint_count(host, address);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        let (blocktime, _) = read_checkpoint(host, address, middle);
+        if blocktime <= timestamp {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+
+    let votes = match low.checked_sub(1) {
+        Some(index) => read_checkpoint(host, address, index).1,
+        None => TokenAmount::zero(),
+    };
+
+    Ok(votes)
+}
+
+/// Records a new voting power of an address at the current block time.
+fn write_votes<H: Host>(host: &H, address: &Address, votes: TokenAmount) -> Result<(), Error> {
+    let blocktime = host.get_blocktime();
+    let count = read_checkpoint_count(host, address);
+
+    if let Some(last) = count.checked_sub(1) {
+        // Multiple changes within the same block are folded into a single checkpoint.
+        if read_checkpoint(host, address, last).0 == blocktime {
+            write_checkpoint(host, address, last, (blocktime, votes));
+            return Ok(());
+        }
+    }
+
+    write_checkpoint(host, address, count, (blocktime, votes));
+    let new_count = count.checked_add(1).ok_or(Error::Overflow)?;
+    write_checkpoint_count(host, address, new_count);
+
+    Ok(())
+}
+
+/// Moves `amount` of voting power from one delegatee to another.
+///
+/// Either side is `None` when the tokens are minted or burned, or when their holder never delegated.
+pub fn move_voting_power<H: Host>(
+    host: &H,
+    from: Option<Address>,
+    to: Option<Address>,
+    amount: TokenAmount,
+) -> Result<(), Error> {
+    if from == to || amount.is_zero() {
+        return Ok(());
+    }
+
+    if let Some(from) = from {
+        let votes = read_votes(host, &from)
+            .checked_sub(amount)
+            .ok_or(Error::Underflow)?;
+        write_votes(host, &from, votes)?;
+    }
+
+    if let Some(to) = to {
+        let votes = read_votes(host, &to)
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        write_votes(host, &to, votes)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::account::AccountHash;
+
+    use super::*;
+    use crate::{internal, mock::MockHost};
+
+    const ALICE: Address = Address::Account(AccountHash::new([1; 32]));
+
+    #[test]
+    fn should_record_one_checkpoint_per_block_time() {
+        let host = MockHost::new();
+        write_delegate(&host, &ALICE, &ALICE);
+
+        host.set_blocktime(1000);
+        internal::mint(&host, &ALICE, TokenAmount::from(100)).unwrap();
+        internal::mint(&host, &ALICE, TokenAmount::from(50)).unwrap();
+
+        host.set_blocktime(2000);
+        internal::burn(&host, &ALICE, TokenAmount::from(30)).unwrap();
+
+        assert_eq!(read_checkpoint_count(&host, &ALICE), 2);
+        assert_eq!(
+            read_checkpoint(&host, &ALICE, 0),
+            (1000, TokenAmount::from(150))
+        );
+        assert_eq!(
+            read_checkpoint(&host, &ALICE, 1),
+            (2000, TokenAmount::from(120))
+        );
+        assert_eq!(read_votes(&host, &ALICE), TokenAmount::from(120));
+    }
+
+    #[test]
+    fn should_read_past_votes() {
+        let host = MockHost::new();
+        write_delegate(&host, &ALICE, &ALICE);
+
+        for (blocktime, amount) in [(1000, 10), (2000, 20), (3000, 30), (4000, 40)].iter() {
+            host.set_blocktime(*blocktime);
+            internal::mint(&host, &ALICE, TokenAmount::from(*amount)).unwrap();
+        }
+
+        host.set_blocktime(5000);
+
+        let past_votes = |timestamp| read_past_votes(&host, &ALICE, timestamp).unwrap();
+        assert_eq!(past_votes(999), TokenAmount::zero());
+        assert_eq!(past_votes(1000), TokenAmount::from(10));
+        assert_eq!(past_votes(2999), TokenAmount::from(30));
+        assert_eq!(past_votes(3000), TokenAmount::from(60));
+        assert_eq!(past_votes(4999), TokenAmount::from(100));
+        assert_eq!(
+            read_past_votes(&host, &ALICE, 5000),
+            Err(Error::FutureLookup)
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Implementation of a wrapped native token.
+//!
+//! Wrapping is a mode chosen when the token is installed, and only a wrapped token has the `deposit` and `withdraw` entry points. Tokens are backed one to one by CSPR held in a purse owned by the contract. Depositing CSPR mints an equal amount of tokens to the depositor, and withdrawing burns tokens and sends the CSPR back to the account.
+//!
+//! Every token has to be backed, so a wrapped token starts with no supply and can't be minted by anyone. One token unit is one mote, so its decimals have to match the decimals of CSPR.
+use casper_types::URef;
+
+use crate::{
+    address::Address,
+    amount::{self, TokenAmount},
+    constants::PURSE_KEY,
+    error::Error,
+    host::{Host, UnwrapOrRevert as _},
+    internal,
+};
+
+/// Decimals of CSPR, as one CSPR is 10^9 motes.
+pub const CSPR_DECIMALS: u8 = 9;
+
+#[inline]
+fn get_purse_uref<H: Host>(host: &H) -> URef {
+    host.get_uref(PURSE_KEY)
+}
+
+/// This function makes sure that a wrapped token is installed with no supply that isn't backed by CSPR.
+///
+/// A wrapped token with an `initial_supply`, a `mintable` one, or with `decimals` other than [`CSPR_DECIMALS`] will fail with [`Error::InvalidWrappedToken`].
+pub fn requires_valid_wrapped_token(
+    decimals: u8,
+    initial_supply: TokenAmount,
+    mintable: bool,
+) -> Result<(), Error> {
+    if decimals != CSPR_DECIMALS || !initial_supply.is_zero() || mintable {
+        return Err(Error::InvalidWrappedToken);
+    }
+
+    Ok(())
+}
+
+/// Moves `amount` of CSPR from a `source_purse` into the contract purse, and mints the same amount of tokens to an `owner`.
+pub fn deposit<H: Host>(
+    host: &H,
+    owner: &Address,
+    source_purse: URef,
+    amount: TokenAmount,
+) -> Result<(), Error> {
+    host.transfer_from_purse_to_purse(source_purse, get_purse_uref(host), amount::to_motes(amount))
+        .unwrap_or_revert_on(host);
+
+    internal::mint(host, owner, amount)
+}
+
+/// Burns `amount` of tokens of an `owner`, and sends the same amount of CSPR from the contract purse to it.
+///
+/// CSPR can only be sent to an account, so it fails with [`Error::InvalidContext`] if the `owner` is a contract.
+pub fn withdraw<H: Host>(host: &H, owner: &Address, amount: TokenAmount) -> Result<(), Error> {
+    let account_hash = *owner.as_account_hash().ok_or(Error::InvalidContext)?;
+
+    internal::burn(host, owner, amount)?;
+
+    host.transfer_from_purse_to_account(
+        get_purse_uref(host),
+        account_hash,
+        amount::to_motes(amount),
+    )
+    .unwrap_or_revert_on(host);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, AccessRights, ContractPackageHash, U512};
+
+    use super::*;
+    use crate::{balances, mock::MockHost, total_supply};
+
+    const ALICE_ACCOUNT_HASH: AccountHash = AccountHash::new([1; 32]);
+    const ALICE: Address = Address::Account(ALICE_ACCOUNT_HASH);
+
+    fn source_purse() -> URef {
+        URef::new([7; 32], AccessRights::READ_ADD_WRITE)
+    }
+
+    #[test]
+    fn should_deposit_and_withdraw() {
+        let host = MockHost::new();
+        host.set_purse_balance(source_purse(), U512::from(1000));
+
+        deposit(&host, &ALICE, source_purse(), TokenAmount::from(1000)).unwrap();
+
+        assert_eq!(host.purse_balance(source_purse()), U512::zero());
+        assert_eq!(host.purse_balance(get_purse_uref(&host)), U512::from(1000));
+        assert_eq!(
+            balances::read_balance(&host, &ALICE),
+            TokenAmount::from(1000)
+        );
+
+        withdraw(&host, &ALICE, TokenAmount::from(400)).unwrap();
+
+        assert_eq!(host.purse_balance(get_purse_uref(&host)), U512::from(600));
+        assert_eq!(host.account_balance(ALICE_ACCOUNT_HASH), U512::from(400));
+        assert_eq!(
+            balances::read_balance(&host, &ALICE),
+            TokenAmount::from(600)
+        );
+        assert_eq!(
+            total_supply::read_total_supply(&host),
+            TokenAmount::from(600)
+        );
+    }
+
+    #[test]
+    fn should_not_withdraw_to_contract() {
+        let host = MockHost::new();
+        let contract = Address::from(ContractPackageHash::new([2; 32]));
+
+        assert_eq!(
+            withdraw(&host, &contract, TokenAmount::zero()),
+            Err(Error::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn should_only_allow_wrapped_token_backed_by_cspr() {
+        assert_eq!(
+            requires_valid_wrapped_token(CSPR_DECIMALS, TokenAmount::zero(), false),
+            Ok(())
+        );
+        assert_eq!(
+            requires_valid_wrapped_token(CSPR_DECIMALS, TokenAmount::one(), false),
+            Err(Error::InvalidWrappedToken)
+        );
+        assert_eq!(
+            requires_valid_wrapped_token(CSPR_DECIMALS, TokenAmount::zero(), true),
+            Err(Error::InvalidWrappedToken)
+        );
+        assert_eq!(
+            requires_valid_wrapped_token(8, TokenAmount::zero(), false),
+            Err(Error::InvalidWrappedToken)
+        );
+    }
+}